@@ -1,6 +1,7 @@
-use sylvia::multitest::App;
+use sylvia::multitest::{App, CustomMockApp};
 
 use crate::contract::multitest_utils::CodeId;
+use crate::messages::{CountResponse, CounterMsg, CounterQuery};
 
 use super::custom_module::{CustomApp, CustomModule};
 
@@ -25,3 +26,30 @@ fn test_custom() {
     let count = contract.query_custom().unwrap().count;
     assert_eq!(count, 1);
 }
+
+#[test]
+fn test_custom_mock() {
+    let owner = "owner";
+
+    let app = App::<CustomMockApp<CounterMsg, CounterQuery>>::custom_mock(|router, _, _| {
+        router
+            .custom
+            .query_returns("count", &CountResponse { count: 5 });
+    });
+
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id.instantiate().call(owner).unwrap();
+
+    contract.send_custom().call(owner).unwrap();
+    assert_eq!(app.custom_msgs_sent(), [CounterMsg::Increment {}]);
+
+    let count = contract.query_custom().unwrap().count;
+    assert_eq!(count, 5);
+
+    app.on_custom_query("count", |query| match query {
+        CounterQuery::Count {} => Ok(CountResponse { count: 7 }),
+    });
+    let count = contract.query_custom().unwrap().count;
+    assert_eq!(count, 7);
+}
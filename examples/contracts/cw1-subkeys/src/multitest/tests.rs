@@ -55,7 +55,6 @@ fn get_contract_version_works() {
 }
 
 mod allowance {
-    use cw_multi_test::next_block;
 
     use crate::responses::AllowanceInfo;
     use crate::state::Allowance;
@@ -134,7 +133,7 @@ mod allowance {
             .call(owner)
             .unwrap();
 
-        app.update_block(next_block);
+        app.advance_blocks(1);
 
         // Check allowances work for accounts with balances
         assert_eq!(
@@ -197,7 +196,7 @@ mod allowance {
             .call(owner)
             .unwrap();
 
-        app.update_block(next_block);
+        app.advance_blocks(1);
 
         let batch1 = contract.all_allowances(None, Some(2)).unwrap().allowances;
         assert_eq!(2, batch1.len());
@@ -234,6 +233,73 @@ mod allowance {
             AllowanceInfo::cmp_by_spender
         );
     }
+
+    #[test]
+    fn branches() {
+        let app = App::default();
+
+        let owner = "owner";
+        let spender = "spender";
+
+        let code_id = CodeId::store_code(&app);
+
+        let contract = code_id
+            .instantiate(vec![owner.to_owned()], false)
+            .with_label("Sublist contract")
+            .call(owner)
+            .unwrap();
+
+        let height = app.block_info().height;
+        contract
+            .increase_allowance(
+                spender.to_owned(),
+                coin(10, ATOM),
+                Some(Expiration::AtHeight(height + 1)),
+            )
+            .call(owner)
+            .unwrap();
+
+        let allowance = Allowance {
+            balance: NativeBalance(coins(10, ATOM)),
+            expires: Expiration::AtHeight(height + 1),
+        };
+        let snapshot = app.snapshot();
+
+        // Allowance expires with the next block
+        app.advance_blocks(1);
+        assert_eq!(
+            Allowance::default(),
+            contract.allowance(spender.to_owned()).unwrap()
+        );
+        app.restore(&snapshot);
+
+        // Decreased allowance keeps the expiration
+        contract
+            .decrease_allowance(spender.to_owned(), coin(4, ATOM), None)
+            .call(owner)
+            .unwrap();
+        assert_eq!(
+            Allowance {
+                balance: NativeBalance(coins(6, ATOM)),
+                ..allowance.clone()
+            },
+            contract.allowance(spender.to_owned()).unwrap()
+        );
+        app.restore(&snapshot);
+
+        // Allowance is removed once fully decreased
+        contract
+            .decrease_allowance(spender.to_owned(), coin(10, ATOM), None)
+            .call(owner)
+            .unwrap();
+        assert_eq!(
+            Allowance::default(),
+            contract.allowance(spender.to_owned()).unwrap()
+        );
+        app.restore(&snapshot);
+
+        assert_eq!(allowance, contract.allowance(spender.to_owned()).unwrap());
+    }
 }
 
 mod permissions {
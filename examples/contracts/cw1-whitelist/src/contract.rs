@@ -386,5 +386,16 @@ mod tests {
             let deserialized = from_binary(&serialized).unwrap();
             assert_eq!(ContractQueryMsg::Cw1(original), deserialized);
         }
+
+        #[test]
+        fn implements_cw1_interface() {
+            use cosmwasm_schema::schema_for;
+
+            let missing = cw1::InterfaceApi::schema().missing_messages(
+                &schema_for!(ContractExecMsg),
+                &schema_for!(ContractQueryMsg),
+            );
+            assert!(missing.is_empty(), "Missing cw1 messages: {:?}", missing);
+        }
     }
 }
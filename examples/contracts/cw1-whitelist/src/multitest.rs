@@ -2,14 +2,14 @@
 mod test {
     use cosmwasm_std::{to_binary, WasmMsg};
 
-    use crate::contract::multitest_utils::CodeId;
+    use crate::contract::multitest_utils::{CodeId, Cw1WhitelistContractProxy};
     use crate::cw1::test_utils::Cw1;
     use crate::error::ContractError;
     use crate::responses::AdminListResponse;
     use crate::whitelist;
     use crate::whitelist::test_utils::Whitelist;
     use assert_matches::assert_matches;
-    use sylvia::multitest::App;
+    use sylvia::multitest::{App, Fixture};
 
     #[test]
     fn proxy_freeze_message() {
@@ -18,17 +18,23 @@ mod test {
 
         let owner = "owner";
 
-        let first_contract = code_id
-            .instantiate(vec![owner.to_owned()], true)
-            .with_label("First contract")
-            .call(owner)
-            .unwrap();
-
-        let second_contract = code_id
-            .instantiate(vec![first_contract.contract_addr.to_string()], true)
-            .with_label("Second contract")
-            .call(owner)
-            .unwrap();
+        let (first_contract, second_contract): (
+            Cw1WhitelistContractProxy<_>,
+            Cw1WhitelistContractProxy<_>,
+        ) = Fixture::new(&app)
+            .contract("first", |_| {
+                code_id
+                    .instantiate(vec![owner.to_owned()], true)
+                    .with_label("First contract")
+                    .call(owner)
+            })
+            .contract("second", |deployment| {
+                code_id
+                    .instantiate(vec![deployment.addr("first").into_string()], true)
+                    .with_label("Second contract")
+                    .call(owner)
+            })
+            .build(|deployment| (deployment.proxy("first"), deployment.proxy("second")));
 
         let freeze = whitelist::ExecMsg::Freeze {};
         let freeze = WasmMsg::Execute {
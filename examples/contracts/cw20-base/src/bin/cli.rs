@@ -0,0 +1,11 @@
+use cw20_base::contract::{ContractExecMsg, ContractQueryMsg, InstantiateMsg};
+use sylvia::cli::Cli;
+
+#[cfg(not(tarpaulin_include))]
+fn main() {
+    Cli::new("cw20-base")
+        .instantiate::<InstantiateMsg>()
+        .exec::<ContractExecMsg>()
+        .query::<ContractQueryMsg>()
+        .run();
+}
@@ -74,7 +74,7 @@ pub struct Cw20Base<'a> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_points)]
-#[contract]
+#[contract(slim)]
 #[error(ContractError)]
 #[messages(cw20_allowances as Allowances)]
 #[messages(cw20_marketing as Marketing)]
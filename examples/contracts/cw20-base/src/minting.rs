@@ -1,6 +1,6 @@
 use crate::contract::{Cw20Base, MinterData};
 use crate::error::ContractError;
-use cosmwasm_std::{Response, StdResult, Uint128};
+use cosmwasm_std::{Response, StdError, StdResult, Uint128};
 use cw20_minting::responses::MinterResponse;
 use cw20_minting::Cw20Minting;
 use sylvia::contract;
@@ -38,7 +38,10 @@ impl Cw20Minting for Cw20Base<'_> {
         }
 
         // update supply and enforce cap
-        config.total_supply += amount;
+        config.total_supply = config
+            .total_supply
+            .checked_add(amount)
+            .map_err(StdError::from)?;
         if let Some(limit) = config.get_cap() {
             if config.total_supply > limit {
                 return Err(ContractError::CannotExceedCap);
@@ -1,5 +1,7 @@
 mod allowances_tests;
 mod base_tests;
+#[cfg(feature = "proptest")]
+mod fuzz_tests;
 mod marketing_tests;
 mod minting_tests;
 mod receiver;
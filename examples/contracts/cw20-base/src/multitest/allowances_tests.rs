@@ -1,11 +1,12 @@
+use std::time::Duration;
+
 use cosmwasm_std::{Binary, StdError, Timestamp, Uint128};
 use cw20_allowances::responses::{
     AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo, AllowanceResponse,
     SpenderAllowanceInfo,
 };
-use cw_multi_test::next_block;
 use cw_utils::Expiration;
-use sylvia::multitest::App;
+use sylvia::multitest::{App, CallError};
 
 use crate::allowances::test_utils::Cw20Allowances;
 use crate::contract::multitest_utils::CodeId;
@@ -411,7 +412,10 @@ fn transfer_from_respects_limits() {
         .transfer_from(owner.to_string(), rcpt.to_string(), Uint128::new(33443))
         .call(spender)
         .unwrap_err();
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // let us increase limit, but set the expiration to expire in the next block
     let next_block_height = app.block_info().height + 1;
@@ -426,7 +430,7 @@ fn transfer_from_respects_limits() {
         .unwrap();
 
     // move to next block
-    app.update_block(next_block);
+    app.advance_blocks(1);
 
     // we should now get the expiration error
     let err = contract
@@ -434,7 +438,77 @@ fn transfer_from_respects_limits() {
         .transfer_from(owner.to_string(), rcpt.to_string(), Uint128::new(33443))
         .call(spender)
         .unwrap_err();
-    assert!(matches!(err, ContractError::Expired));
+    assert!(matches!(err, CallError::Contract(ContractError::Expired)));
+}
+
+#[test]
+fn allowance_expires_in_time() {
+    let app = App::default();
+    app.auto_advance_blocks(true);
+
+    let owner = "addr0001";
+    let spender = "addr0002";
+    let rcpt = "addr0003";
+
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(InstantiateMsgData {
+            name: "Auto Gen".to_string(),
+            symbol: "AUTO".to_string(),
+            decimals: 3,
+            initial_balances: vec![Cw20Coin {
+                address: owner.into(),
+                amount: Uint128::new(999999),
+            }],
+            mint: None,
+            marketing: None,
+        })
+        .with_label("Cw20 contract")
+        .call(owner)
+        .unwrap();
+
+    // allowance valid for two more blocks, each call being done in a new block
+    let expires = Expiration::AtHeight(app.block_info().height + 3);
+    contract
+        .cw20_allowances_proxy()
+        .increase_allowance(spender.to_string(), Uint128::new(1000), Some(expires))
+        .call(owner)
+        .unwrap();
+    contract
+        .cw20_allowances_proxy()
+        .transfer_from(owner.to_string(), rcpt.to_string(), Uint128::new(10))
+        .call(spender)
+        .unwrap();
+    let err = contract
+        .cw20_allowances_proxy()
+        .transfer_from(owner.to_string(), rcpt.to_string(), Uint128::new(10))
+        .call(spender)
+        .unwrap_err();
+    assert!(matches!(err, CallError::Contract(ContractError::Expired)));
+
+    // allowance valid for a day
+    let expires = Expiration::AtTime(app.block_info().time.plus_days(1));
+    contract
+        .cw20_allowances_proxy()
+        .increase_allowance(spender.to_string(), Uint128::new(1000), Some(expires))
+        .call(owner)
+        .unwrap();
+
+    app.advance_time(Duration::from_secs(23 * 60 * 60));
+    contract
+        .cw20_allowances_proxy()
+        .transfer_from(owner.to_string(), rcpt.to_string(), Uint128::new(10))
+        .call(spender)
+        .unwrap();
+
+    app.advance_time(Duration::from_secs(60 * 60));
+    let err = contract
+        .cw20_allowances_proxy()
+        .transfer_from(owner.to_string(), rcpt.to_string(), Uint128::new(10))
+        .call(spender)
+        .unwrap_err();
+    assert!(matches!(err, CallError::Contract(ContractError::Expired)));
 }
 
 #[test]
@@ -507,7 +581,10 @@ fn burn_from_respects_limits() {
         .call(spender)
         .unwrap_err();
 
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // let us increase limit, but set the expiration to expire in the next block
     let next_block_height = app.block_info().height + 1;
@@ -522,7 +599,7 @@ fn burn_from_respects_limits() {
         .unwrap();
 
     // move to next block
-    app.update_block(next_block);
+    app.advance_blocks(1);
 
     // we should now get the expiration error
     let err = contract
@@ -530,7 +607,7 @@ fn burn_from_respects_limits() {
         .burn_from(owner.to_string(), Uint128::new(33443))
         .call(spender)
         .unwrap_err();
-    assert!(matches!(err, ContractError::Expired));
+    assert!(matches!(err, CallError::Contract(ContractError::Expired)));
 }
 
 // Ignoring currently due to some issue with unsupported msg being sent in send_from
@@ -622,7 +699,10 @@ fn send_from_respects_limits() {
         )
         .call(spender)
         .unwrap_err();
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // let us increase limit, but set the expiration to expire in the next block
     let next_block_height = app.block_info().height + 1;
@@ -637,7 +717,7 @@ fn send_from_respects_limits() {
         .unwrap();
 
     // move to next block
-    app.update_block(next_block);
+    app.advance_blocks(1);
 
     // we should now get the expiration error
     let err = contract
@@ -651,7 +731,7 @@ fn send_from_respects_limits() {
         .call(spender)
         .unwrap_err();
 
-    assert!(matches!(err, ContractError::Expired));
+    assert!(matches!(err, CallError::Contract(ContractError::Expired)));
 }
 
 #[test]
@@ -692,7 +772,7 @@ fn no_past_expiration() {
         .unwrap_err();
 
     // ensure it is rejected
-    assert_eq!(ContractError::InvalidExpiration, err);
+    assert_eq!(err, ContractError::InvalidExpiration);
 
     // set allowance with time expiration in the past
     let block_time = app.block_info().time;
@@ -705,7 +785,7 @@ fn no_past_expiration() {
         .unwrap_err();
 
     // ensure it is rejected
-    assert_eq!(ContractError::InvalidExpiration, err);
+    assert_eq!(err, ContractError::InvalidExpiration);
 
     // set allowance with height expiration at next block height
     let block_height = app.block_info().height + 1;
@@ -760,7 +840,7 @@ fn no_past_expiration() {
         .unwrap_err();
 
     // ensure it is rejected
-    assert_eq!(ContractError::InvalidExpiration, err);
+    assert_eq!(err, ContractError::InvalidExpiration);
 
     // decrease with height expiration at next block height
     let block_height = app.block_info().height + 1;
@@ -1,7 +1,7 @@
 use cosmwasm_std::{Addr, Binary, StdError, Uint128};
 use cw20_allowances::responses::{AllAllowancesResponse, SpenderAllowanceInfo};
 use cw_utils::Expiration;
-use sylvia::multitest::App;
+use sylvia::multitest::{App, CallError};
 
 use crate::allowances::test_utils::Cw20Allowances;
 use crate::contract::multitest_utils::CodeId;
@@ -219,7 +219,10 @@ fn transfer() {
         .call(owner)
         .unwrap_err();
 
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // cannot send from empty account
     let err = contract
@@ -227,7 +230,10 @@ fn transfer() {
         .call(addr)
         .unwrap_err();
 
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // valid transfer
     contract
@@ -284,14 +290,20 @@ fn burn() {
     // cannot burn more than we have
     let err = contract.burn(too_much).call(owner).unwrap_err();
 
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
     let resp = contract.token_info().unwrap();
     assert_eq!(resp.total_supply, amount);
 
     // cannot send from empty account
     let err = contract.burn(burn).call(addr).unwrap_err();
 
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // valid burn reduces total supply
     contract.burn(burn).call(owner).unwrap();
@@ -360,7 +372,10 @@ fn send() {
         )
         .call(owner)
         .unwrap_err();
-    assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
 
     // valid transfer
     contract
@@ -0,0 +1,61 @@
+use cosmwasm_std::Uint128;
+use cw20_minting::responses::MinterResponse;
+use sylvia::anyhow::ensure;
+use sylvia::fuzz::Fuzzer;
+
+use crate::allowances::test_utils::Cw20Allowances;
+use crate::contract::multitest_utils::{CodeId, Cw20BaseProxy};
+use crate::contract::{ContractExecMsg, InstantiateMsgData};
+use crate::responses::Cw20Coin;
+
+#[test]
+fn total_supply_matches_balances() {
+    Fuzzer::<ContractExecMsg>::new()
+        .with_senders(["owner", "minter", "spender"])
+        .invariant("total supply", |app, contract| {
+            let contract = Cw20BaseProxy::new(contract.clone(), app);
+
+            let mut balances = Uint128::zero();
+            let mut start_after = None;
+            loop {
+                let accounts = contract
+                    .cw20_allowances_proxy()
+                    .all_accounts(start_after, Some(30))?
+                    .accounts;
+                for account in &accounts {
+                    balances += contract.balance(account.clone())?.balance;
+                }
+                match accounts.last() {
+                    Some(last) => start_after = Some(last.clone()),
+                    None => break,
+                }
+            }
+
+            let total_supply = contract.token_info()?.total_supply;
+            ensure!(
+                total_supply == balances,
+                "total supply {total_supply} differs from sum of balances {balances}"
+            );
+            Ok(())
+        })
+        .run(|app| {
+            CodeId::store_code(app)
+                .instantiate(InstantiateMsgData {
+                    name: "Cash Token".to_string(),
+                    symbol: "CASH".to_string(),
+                    decimals: 9,
+                    initial_balances: vec![Cw20Coin {
+                        address: "owner".to_string(),
+                        amount: Uint128::new(1000),
+                    }],
+                    mint: Some(MinterResponse {
+                        minter: "minter".to_string(),
+                        cap: None,
+                    }),
+                    marketing: None,
+                })
+                .call("owner")
+                .unwrap()
+                .contract_addr
+        });
+}
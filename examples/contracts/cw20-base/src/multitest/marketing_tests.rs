@@ -1,7 +1,7 @@
 use cosmwasm_std::{Addr, StdError};
 use cw20_marketing::responses::{DownloadLogoResponse, LogoInfo, MarketingInfoResponse};
 use cw20_marketing::{EmbeddedLogo, Logo};
-use sylvia::multitest::App;
+use sylvia::multitest::{App, CallError};
 
 use crate::contract::multitest_utils::CodeId;
 use crate::contract::{InstantiateMarketingInfo, InstantiateMsgData};
@@ -360,7 +360,7 @@ fn update_marketing_invalid() {
         .unwrap_err();
 
     assert!(
-        matches!(err, ContractError::Std(_)),
+        matches!(err, CallError::Contract(ContractError::Std(_))),
         "Expected Std error, received: {}",
         err
     );
@@ -1,6 +1,6 @@
 use cosmwasm_std::{StdError, Uint128};
 use cw20_minting::responses::MinterResponse;
-use sylvia::multitest::App;
+use sylvia::multitest::{App, CallError};
 
 use crate::contract::multitest_utils::CodeId;
 use crate::contract::InstantiateMsgData;
@@ -109,6 +109,46 @@ fn mintable_over_cap() {
     );
 }
 
+#[test]
+fn mint_overflowing_supply() {
+    let app = App::default();
+
+    let owner = "addr0001";
+    let minter = "addr0002";
+
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(InstantiateMsgData {
+            name: "Cash Token".to_string(),
+            symbol: "CASH".to_string(),
+            decimals: 9,
+            initial_balances: vec![Cw20Coin {
+                address: owner.to_string(),
+                amount: Uint128::new(1000),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.to_string(),
+                cap: None,
+            }),
+            marketing: None,
+        })
+        .with_label("Cw20 contract")
+        .call(owner)
+        .unwrap();
+
+    let err = contract
+        .cw20_minting_proxy()
+        .mint(owner.to_string(), Uint128::MAX)
+        .call(minter)
+        .unwrap_err();
+
+    assert!(matches!(
+        err,
+        CallError::Contract(ContractError::Std(StdError::Overflow { .. }))
+    ));
+}
+
 #[test]
 fn can_mint_by_minter() {
     let app = App::default();
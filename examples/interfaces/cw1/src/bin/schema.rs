@@ -0,0 +1,6 @@
+#[cfg(not(tarpaulin_include))]
+fn main() {
+    cw1::InterfaceApi::schema()
+        .export("schema")
+        .expect("Failed to export cw1 interface schema");
+}
@@ -0,0 +1,208 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Type, WherePredicate};
+
+use crate::crate_module;
+use crate::message::{MsgField, MsgVariant};
+
+/// Emits `sylvia::cli::CliMsg` implementations for generated messages.
+///
+/// Implementations are emitted only with the `cli` feature and never on wasm.
+pub struct CliMsg<'a> {
+    name: &'a Ident,
+    generics: &'a TokenStream,
+    wheres: &'a [&'a WherePredicate],
+}
+
+impl<'a> CliMsg<'a> {
+    pub fn new(
+        name: &'a Ident,
+        generics: &'a TokenStream,
+        wheres: &'a [&'a WherePredicate],
+    ) -> Self {
+        Self {
+            name,
+            generics,
+            wheres,
+        }
+    }
+
+    /// Struct message fields become arguments of the command
+    pub fn emit_struct(&self, fields: &[MsgField]) -> TokenStream {
+        if !cfg!(feature = "cli") {
+            return quote! {};
+        }
+
+        let args = fields.iter().map(emit_field_arg);
+        let fields_parsing = fields.iter().map(emit_field_parsing);
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            self.emit_impl(
+                fields.iter().map(MsgField::ty),
+                quote! { cmd #(.arg(#args))* },
+                quote! { Ok(Self { #(#fields_parsing,)* }) },
+            )
+        }
+    }
+
+    /// Every variant of enum message becomes a subcommand with its fields as arguments
+    pub fn emit_enum(&self, variants: &[MsgVariant]) -> TokenStream {
+        if !cfg!(feature = "cli") {
+            return quote! {};
+        }
+
+        let sylvia = crate_module();
+
+        let subcommands = variants.iter().map(|variant| {
+            let msg_name = variant.name().to_string().to_case(Case::Snake);
+            let args = variant.fields().iter().map(emit_field_arg);
+
+            quote! {
+                #sylvia ::clap::Command::new(#msg_name) #(.arg(#args))*
+            }
+        });
+
+        let match_arms = variants.iter().map(|variant| {
+            let name = variant.name();
+            let msg_name = name.to_string().to_case(Case::Snake);
+            let fields_parsing = variant.fields().iter().map(emit_field_parsing);
+
+            quote! {
+                Some((#msg_name, matches)) => Ok(Self:: #name { #(#fields_parsing,)* })
+            }
+        });
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            self.emit_impl(
+                variants
+                    .iter()
+                    .flat_map(|variant| variant.fields().iter().map(MsgField::ty)),
+                quote! { cmd #(.subcommand(#subcommands))* },
+                quote! {
+                    match matches.subcommand() {
+                        #(#match_arms,)*
+                        _ => Err(#sylvia ::cli::unknown_message(matches)),
+                    }
+                },
+            )
+        }
+    }
+
+    /// Glue message merges subcommands of all its messages and forwards parsing to the message
+    /// owning the picked subcommand
+    pub fn emit_glue(&self, variants: &[(TokenStream, TokenStream)]) -> TokenStream {
+        if !cfg!(feature = "cli") {
+            return quote! {};
+        }
+
+        let sylvia = crate_module();
+        let name = self.name;
+
+        let augments = variants.iter().map(|(_, ty)| {
+            quote! { let cmd = <#ty as #sylvia ::cli::CliMsg>::augment_command(cmd); }
+        });
+
+        let match_arms = variants.iter().map(|(variant, ty)| {
+            quote! {
+                Some(msg_name) if <#ty>::is_message(msg_name) => {
+                    <#ty as #sylvia ::cli::CliMsg>::from_matches(matches).map(#name :: #variant)
+                }
+            }
+        });
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                impl #sylvia ::cli::CliMsg for #name {
+                    fn augment_command(cmd: #sylvia ::clap::Command) -> #sylvia ::clap::Command {
+                        #(#augments)*
+                        cmd
+                    }
+
+                    fn from_matches(matches: &#sylvia ::clap::ArgMatches) -> Result<Self, #sylvia ::clap::Error> {
+                        match matches.subcommand_name() {
+                            #(#match_arms,)*
+                            _ => Err(#sylvia ::cli::unknown_message(matches)),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn emit_impl<'t>(
+        &self,
+        fields_types: impl Iterator<Item = &'t Type>,
+        augment_command: TokenStream,
+        from_matches: TokenStream,
+    ) -> TokenStream {
+        let sylvia = crate_module();
+        let Self {
+            name,
+            generics,
+            wheres,
+        } = self;
+
+        // Bounding fields types directly covers generic fields without inspecting generics
+        let fields_types =
+            fields_types.map(|ty| quote! { #ty: #sylvia ::serde::de::DeserializeOwned });
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                impl #generics #sylvia ::cli::CliMsg for #name #generics
+                    where #(#wheres,)* #(#fields_types,)*
+                {
+                    fn augment_command(cmd: #sylvia ::clap::Command) -> #sylvia ::clap::Command {
+                        #augment_command
+                    }
+
+                    #[allow(unused_variables)]
+                    fn from_matches(matches: &#sylvia ::clap::ArgMatches) -> Result<Self, #sylvia ::clap::Error> {
+                        #from_matches
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn emit_field_arg(field: &MsgField) -> TokenStream {
+    let sylvia = crate_module();
+    let ty = field.ty();
+    let id = field.name().to_string();
+    let flag = id.to_case(Case::Kebab);
+    let value_name = quote! { #ty }.to_string().replace(' ', "");
+    let optional = is_option(ty);
+
+    quote! {
+        #sylvia ::cli::field_arg(#id, #flag, #value_name, #optional)
+    }
+}
+
+fn emit_field_parsing(field: &MsgField) -> TokenStream {
+    let sylvia = crate_module();
+    let name = field.name();
+    let id = name.to_string();
+
+    quote! {
+        #name: #sylvia ::cli::parse_field(matches, #id)?
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
@@ -0,0 +1,116 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, WherePredicate};
+
+use crate::crate_module;
+use crate::message::{MsgField, MsgVariant};
+
+/// Emits `sylvia::fuzz::Generate` implementations for generated enum messages.
+///
+/// Implementations are emitted only with the `proptest` feature and never on wasm.
+pub struct Generate<'a> {
+    name: &'a Ident,
+    generics: &'a TokenStream,
+    wheres: &'a [&'a WherePredicate],
+}
+
+impl<'a> Generate<'a> {
+    pub fn new(
+        name: &'a Ident,
+        generics: &'a TokenStream,
+        wheres: &'a [&'a WherePredicate],
+    ) -> Self {
+        Self {
+            name,
+            generics,
+            wheres,
+        }
+    }
+
+    /// Every variant gets its own strategy generating its fields
+    pub fn emit_enum(&self, variants: &[MsgVariant]) -> TokenStream {
+        if !cfg!(feature = "proptest") {
+            return quote! {};
+        }
+
+        let sylvia = crate_module();
+        let Self {
+            name,
+            generics,
+            wheres,
+        } = self;
+
+        let strategies = variants.iter().map(|variant| {
+            let msg_name = variant.name().to_string().to_case(Case::Snake);
+            let fields = variant.fields().iter().map(|field| {
+                let name = field.name().to_string();
+                let ty = field.ty();
+                quote! { (#name, ctx.field::<#ty>()) }
+            });
+
+            quote! {
+                ctx.message::<Self>(#msg_name, vec![#(#fields),*])
+            }
+        });
+
+        // Bounding fields types directly covers generic fields without inspecting generics
+        let fields_types = variants
+            .iter()
+            .flat_map(|variant| variant.fields().iter().map(MsgField::ty))
+            .map(|ty| {
+                quote! { #ty: #sylvia ::schemars::JsonSchema + #sylvia ::serde::de::DeserializeOwned }
+            });
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                impl #generics #sylvia ::fuzz::Generate for #name #generics
+                    where
+                        #(#wheres,)*
+                        #(#fields_types,)*
+                        Self: #sylvia ::serde::de::DeserializeOwned + std::fmt::Debug + 'static,
+                {
+                    #[allow(unused_variables)]
+                    fn strategies(ctx: &#sylvia ::fuzz::GenContext) -> Vec<#sylvia ::proptest::strategy::BoxedStrategy<Self>> {
+                        vec![#(#strategies),*]
+                    }
+                }
+            }
+        }
+    }
+
+    /// Glue message generates messages of all its variants
+    pub fn emit_glue(&self, variants: &[(TokenStream, TokenStream)]) -> TokenStream {
+        if !cfg!(feature = "proptest") {
+            return quote! {};
+        }
+
+        let sylvia = crate_module();
+        let name = self.name;
+
+        let strategies = variants.iter().map(|(variant, ty)| {
+            quote! {
+                strategies.extend(#sylvia ::fuzz::map_strategies(
+                    <#ty as #sylvia ::fuzz::Generate>::strategies(ctx),
+                    #name :: #variant,
+                ));
+            }
+        });
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                impl #sylvia ::fuzz::Generate for #name {
+                    fn strategies(ctx: &#sylvia ::fuzz::GenContext) -> Vec<#sylvia ::proptest::strategy::BoxedStrategy<Self>> {
+                        let mut strategies = vec![];
+                        #(#strategies)*
+                        strategies
+                    }
+                }
+            }
+        }
+    }
+}
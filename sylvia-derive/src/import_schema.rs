@@ -0,0 +1,740 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use convert_case::{Case, Casing};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use serde_json::{Map, Value};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse2, Ident, LitStr};
+
+use crate::crate_module;
+
+/// Parsed `import_schema!` input - path to the API JSON relative to the crate manifest
+pub struct ImportSchema {
+    path: LitStr,
+}
+
+impl Parse for ImportSchema {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            path: input.parse()?,
+        })
+    }
+}
+
+impl ImportSchema {
+    pub fn process(input: TokenStream) -> syn::Result<TokenStream> {
+        let Self { path } = parse2(input)?;
+        let span = path.span();
+
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| syn::Error::new(span, "CARGO_MANIFEST_DIR is not set"))?;
+        let full_path = PathBuf::from(manifest_dir).join(path.value());
+        let content = std::fs::read_to_string(&full_path).map_err(|err| {
+            syn::Error::new(
+                span,
+                format!("Cannot read schema {}: {}", full_path.display(), err),
+            )
+        })?;
+        let api: Value = serde_json::from_str(&content)
+            .map_err(|err| syn::Error::new(span, format!("Invalid schema JSON: {}", err)))?;
+
+        let bindings = SchemaBindings::new(&api, span)?;
+        let full_path = full_path.to_string_lossy();
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            Ok(quote! {
+                // Makes the compiler rebuild bindings whenever the schema changes
+                const _: &[u8] = include_bytes!(#full_path);
+
+                #bindings
+            })
+        }
+    }
+}
+
+/// Interface trait and types generated from the API JSON
+struct SchemaBindings {
+    types: Vec<TokenStream>,
+    interface: TokenStream,
+}
+
+impl SchemaBindings {
+    fn new(api: &Value, span: Span) -> syn::Result<Self> {
+        let sylvia = crate_module();
+        let mut types = TypesGenerator::new(span);
+
+        let contract_name = api
+            .get("contract_name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| syn::Error::new(span, "Schema is missing `contract_name`"))?;
+        let trait_name = type_ident(contract_name, span)?;
+
+        for root in ["instantiate", "migrate", "sudo"] {
+            if let Some(schema) = api.get(root).filter(|schema| !schema.is_null()) {
+                types.add_root(schema);
+                types.root_type(schema)?;
+            }
+        }
+
+        let mut methods = vec![];
+        let mut aliases = vec![];
+        if let Some(execute) = api.get("execute").filter(|schema| !schema.is_null()) {
+            types.add_root(execute);
+            for (name, payload) in messages(execute) {
+                let (name, params) = message_signature(&mut types, &name, &payload, span)?;
+                methods.push(quote! {
+                    #[msg(exec)]
+                    fn #name(&self, ctx: #sylvia ::types::ExecCtx, #(#params),*) -> Result<#sylvia ::cw_std::Response, Self::Error>;
+                });
+            }
+        }
+
+        if let Some(query) = api.get("query").filter(|schema| !schema.is_null()) {
+            types.add_root(query);
+            let responses = api.get("responses").and_then(Value::as_object);
+            for (name, payload) in messages(query) {
+                let response = responses
+                    .and_then(|responses| responses.get(&name))
+                    .ok_or_else(|| {
+                        syn::Error::new(span, format!("Missing response of `{}` query", name))
+                    })?;
+                types.add_root(response);
+                let response = types.root_type(response)?;
+
+                // Interface requires response types to be single segment paths, so the
+                // `cosmwasm_std` types have to be aliased
+                let response = match syn::parse2(response.clone()) {
+                    Ok(syn::Type::Path(path)) if path.path.segments.len() > 1 => {
+                        let alias = type_ident(&format!("{}Response", name), span)?;
+                        aliases.push(quote! { pub type #alias = #response; });
+                        quote! { #alias }
+                    }
+                    _ => response,
+                };
+                let (name, params) = message_signature(&mut types, &name, &payload, span)?;
+                methods.push(quote! {
+                    #[msg(query)]
+                    fn #name(&self, ctx: #sylvia ::types::QueryCtx, #(#params),*) -> Result<#response, Self::Error>;
+                });
+            }
+        }
+
+        let interface = quote! {
+            pub trait #trait_name {
+                type Error: From<#sylvia ::cw_std::StdError>;
+
+                #(#methods)*
+            }
+        };
+
+        let mut types = types.emit()?;
+        types.extend(aliases);
+
+        Ok(Self {
+            types,
+            interface: crate::interface_impl(quote! {}, interface),
+        })
+    }
+}
+
+impl quote::ToTokens for SchemaBindings {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { types, interface } = self;
+
+        tokens.extend(quote! {
+            #(#types)*
+
+            #interface
+        })
+    }
+}
+
+/// Converts message payload into the trait method name and parameters
+fn message_signature(
+    types: &mut TypesGenerator,
+    name: &str,
+    payload: &Value,
+    span: Span,
+) -> syn::Result<(Ident, Vec<TokenStream>)> {
+    // Sylvia serializes messages as `snake_case` of the variant, so only such names can be
+    // expressed as interface methods
+    if name.to_case(Case::UpperCamel).to_case(Case::Snake) != name {
+        return Err(syn::Error::new(
+            span,
+            format!("Message `{}` is not a snake_case name", name),
+        ));
+    }
+
+    let Some(payload) = payload.as_object().filter(|payload| is_struct(payload)) else {
+        return Err(syn::Error::new(
+            span,
+            format!("Message `{}` has to be a struct variant", name),
+        ));
+    };
+
+    // Field attributes are forwarded to the generated message, so renamed fields keep their
+    // JSON names
+    let params = types
+        .fields(payload)?
+        .into_iter()
+        .map(|Field { name, ty, attrs }| quote! { #attrs #name: #ty })
+        .collect();
+
+    // Messages are named after the methods, so they cannot be renamed
+    match field_ident(name, span) {
+        (ident, None) => Ok((ident, params)),
+        (_, Some(_)) => Err(syn::Error::new(
+            span,
+            format!("Message `{}` cannot be a method name", name),
+        )),
+    }
+}
+
+/// Collects messages of the root schema along with their payloads.
+///
+/// Variants of glue messages generated by sylvia are resolved through `$ref`s.
+fn messages(root: &Value) -> Vec<(String, Value)> {
+    fn collect(
+        schema: &Value,
+        definitions: &Value,
+        visited: &mut BTreeSet<String>,
+        messages: &mut Vec<(String, Value)>,
+    ) {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = definition_name(reference);
+            if visited.insert(name.to_owned()) {
+                if let Some(def) = definitions.get(name) {
+                    collect(def, definitions, visited, messages);
+                }
+            }
+            return;
+        }
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+                variants
+                    .iter()
+                    .for_each(|variant| collect(variant, definitions, visited, messages));
+            }
+        }
+
+        if let Some((name, payload)) = externally_tagged(schema) {
+            messages.push((name.to_owned(), payload.clone()));
+        }
+
+        // Unit variants are collected only to be reported as unsupported
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            values
+                .iter()
+                .filter_map(Value::as_str)
+                .for_each(|name| messages.push((name.to_owned(), Value::Null)));
+        }
+    }
+
+    let definitions = root.get("definitions").cloned().unwrap_or(Value::Null);
+    let mut messages = vec![];
+    collect(root, &definitions, &mut BTreeSet::new(), &mut messages);
+    messages
+}
+
+/// Returns variant name and payload of the externally tagged enum variant
+fn externally_tagged(schema: &Value) -> Option<(&str, &Value)> {
+    let required = schema.get("required")?.as_array()?;
+    let properties = schema.get("properties")?.as_object()?;
+    match (required.as_slice(), properties.len()) {
+        ([name], 1) => {
+            let name = name.as_str()?;
+            properties.get(name).map(|payload| (name, payload))
+        }
+        _ => None,
+    }
+}
+
+fn definition_name(reference: &str) -> &str {
+    reference.trim_start_matches("#/definitions/")
+}
+
+struct Field {
+    name: Ident,
+    ty: TokenStream,
+    attrs: TokenStream,
+}
+
+/// Generates Rust types for the schema definitions.
+///
+/// Only definitions actually used by the messages are generated, and well known CosmWasm
+/// types are mapped to the `cosmwasm_std` ones.
+struct TypesGenerator {
+    span: Span,
+    definitions: BTreeMap<String, Value>,
+    used: BTreeSet<String>,
+}
+
+impl TypesGenerator {
+    fn new(span: Span) -> Self {
+        Self {
+            span,
+            definitions: BTreeMap::new(),
+            used: BTreeSet::new(),
+        }
+    }
+
+    fn add_root(&mut self, root: &Value) {
+        if let Some(definitions) = root.get("definitions").and_then(Value::as_object) {
+            self.definitions.extend(definitions.clone());
+        }
+    }
+
+    /// Returns type of the root schema - named by its title if it requires a new type
+    fn root_type(&mut self, root: &Value) -> syn::Result<TokenStream> {
+        let title = root.get("title").and_then(Value::as_str);
+        if let Some(known) = title.and_then(known_type) {
+            return Ok(known);
+        }
+
+        match title {
+            Some(title) if requires_definition(root) => {
+                let mut schema = root.clone();
+                if let Some(schema) = schema.as_object_mut() {
+                    schema.remove("definitions");
+                    schema.remove("$schema");
+                }
+                self.definitions.entry(title.to_owned()).or_insert(schema);
+                self.used.insert(title.to_owned());
+                let ident = type_ident(title, self.span)?;
+                Ok(quote! { #ident })
+            }
+            _ => self.rust_type(root),
+        }
+    }
+
+    fn rust_type(&mut self, schema: &Value) -> syn::Result<TokenStream> {
+        let schema = match schema {
+            Value::Object(schema) => schema,
+            _ => return Err(self.unsupported("untyped values")),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = definition_name(reference);
+            if let Some(known) = known_type(name) {
+                return Ok(known);
+            }
+            if !self.definitions.contains_key(name) {
+                return Err(syn::Error::new(
+                    self.span,
+                    format!("Missing definition of `{}`", name),
+                ));
+            }
+            self.used.insert(name.to_owned());
+            let ident = type_ident(name, self.span)?;
+            return Ok(quote! { #ident });
+        }
+
+        if let Some([single]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            return self.rust_type(single);
+        }
+
+        if let Some(variants) = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(Value::as_array)
+        {
+            return match variants.as_slice() {
+                [ty, null] | [null, ty] if is_null(null) => {
+                    let ty = self.rust_type(ty)?;
+                    Ok(quote! { Option<#ty> })
+                }
+                _ => Err(self.unsupported("inline enums")),
+            };
+        }
+
+        match schema.get("type") {
+            Some(Value::String(ty)) => self.primitive_type(ty, schema),
+            Some(Value::Array(types)) => match types.as_slice() {
+                [Value::String(ty), Value::String(null)] if null == "null" => {
+                    let ty = self.primitive_type(ty, schema)?;
+                    Ok(quote! { Option<#ty> })
+                }
+                _ => Err(self.unsupported("multiple types")),
+            },
+            _ => Err(self.unsupported("untyped values")),
+        }
+    }
+
+    fn primitive_type(
+        &mut self,
+        ty: &str,
+        schema: &Map<String, Value>,
+    ) -> syn::Result<TokenStream> {
+        let format = schema.get("format").and_then(Value::as_str);
+
+        let ty = match (ty, format) {
+            ("boolean", _) => quote! { bool },
+            ("string", _) if schema.contains_key("enum") => {
+                return Err(self.unsupported("inline enums"))
+            }
+            ("string", _) => quote! { String },
+            ("integer", Some("uint8")) => quote! { u8 },
+            ("integer", Some("uint16")) => quote! { u16 },
+            ("integer", Some("uint32")) => quote! { u32 },
+            ("integer", Some("uint64")) => quote! { u64 },
+            ("integer", Some("uint128")) => quote! { u128 },
+            ("integer", Some("int8")) => quote! { i8 },
+            ("integer", Some("int16")) => quote! { i16 },
+            ("integer", Some("int32")) => quote! { i32 },
+            ("integer", Some("int128")) => quote! { i128 },
+            ("integer", _) => quote! { i64 },
+            ("number", Some("float")) => quote! { f32 },
+            ("number", _) => quote! { f64 },
+            ("array", _) => match schema.get("items") {
+                Some(Value::Array(items)) => {
+                    let items = items
+                        .iter()
+                        .map(|item| self.rust_type(item))
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    quote! { (#(#items,)*) }
+                }
+                Some(item) => {
+                    let item = self.rust_type(item)?;
+                    quote! { Vec<#item> }
+                }
+                None => return Err(self.unsupported("untyped arrays")),
+            },
+            ("object", _) => match schema.get("additionalProperties") {
+                Some(value @ Value::Object(_)) if !schema.contains_key("properties") => {
+                    let value = self.rust_type(value)?;
+                    quote! { std::collections::BTreeMap<String, #value> }
+                }
+                _ => return Err(self.unsupported("inline structs")),
+            },
+            ("null", _) => quote! { () },
+            (ty, _) => return Err(self.unsupported(&format!("`{}` type", ty))),
+        };
+
+        Ok(ty)
+    }
+
+    fn fields(&mut self, object: &Map<String, Value>) -> syn::Result<Vec<Field>> {
+        let required: BTreeSet<_> = object
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let Some(properties) = object.get("properties").and_then(Value::as_object) else {
+            return Ok(vec![]);
+        };
+
+        properties
+            .iter()
+            .map(|(name, schema)| {
+                let (ident, rename) = field_ident(name, self.span);
+                let mut ty = self.rust_type(schema)?;
+                let mut attrs = quote! { #rename };
+                if !required.contains(name.as_str()) {
+                    if !is_nullable(schema) {
+                        ty = quote! { Option<#ty> };
+                    }
+                    attrs.extend(quote! { #[serde(default)] });
+                }
+
+                Ok(Field {
+                    name: ident,
+                    ty,
+                    attrs,
+                })
+            })
+            .collect()
+    }
+
+    /// Emits all the used definitions, including the ones used only by other definitions
+    fn emit(mut self) -> syn::Result<Vec<TokenStream>> {
+        let mut emitted = BTreeSet::new();
+        let mut types = vec![];
+
+        while let Some(name) = self
+            .used
+            .iter()
+            .find(|name| !emitted.contains(*name))
+            .cloned()
+        {
+            let schema = self.definitions[&name].clone();
+            types.push(self.emit_definition(&name, &schema)?);
+            emitted.insert(name);
+        }
+
+        Ok(types)
+    }
+
+    fn emit_definition(&mut self, name: &str, schema: &Value) -> syn::Result<TokenStream> {
+        let sylvia = crate_module();
+        let ident = type_ident(name, self.span)?;
+        let doc = schema
+            .get("description")
+            .and_then(Value::as_str)
+            .map(|doc| quote! { #[doc = #doc] });
+        let derive = quote! {
+            #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema)]
+        };
+
+        if let Some(variants) = enum_variants(schema) {
+            let variants = variants
+                .into_iter()
+                .map(|variant| self.emit_variant(variant))
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            #[cfg(not(tarpaulin_include))]
+            return Ok(quote! {
+                #doc
+                #derive
+                pub enum #ident {
+                    #(#variants,)*
+                }
+            });
+        }
+
+        match schema.as_object() {
+            Some(object) if object.contains_key("properties") => {
+                let deny_unknown_fields = object
+                    .get("additionalProperties")
+                    .and_then(Value::as_bool)
+                    .filter(|allowed| !allowed)
+                    .map(|_| quote! { #[serde(deny_unknown_fields)] });
+                let fields = self
+                    .fields(object)?
+                    .into_iter()
+                    .map(|Field { name, ty, attrs }| quote! { #attrs pub #name: #ty });
+
+                #[cfg(not(tarpaulin_include))]
+                Ok(quote! {
+                    #doc
+                    #derive
+                    #deny_unknown_fields
+                    pub struct #ident {
+                        #(#fields,)*
+                    }
+                })
+            }
+            _ => {
+                let ty = self.rust_type(schema)?;
+
+                #[cfg(not(tarpaulin_include))]
+                Ok(quote! {
+                    #doc
+                    pub type #ident = #ty;
+                })
+            }
+        }
+    }
+
+    fn emit_variant(&mut self, variant: Variant) -> syn::Result<TokenStream> {
+        let ident = type_ident(variant.name, self.span)?;
+        let name = variant.name;
+        let doc = variant.doc.map(|doc| quote! { #[doc = #doc] });
+
+        let payload = match variant.payload {
+            None => quote! {},
+            Some(Value::Object(payload)) if is_struct(payload) => {
+                let fields = self
+                    .fields(payload)?
+                    .into_iter()
+                    .map(|Field { name, ty, attrs }| quote! { #attrs #name: #ty });
+                quote! { { #(#fields,)* } }
+            }
+            Some(payload) => {
+                let ty = self.rust_type(payload)?;
+                quote! { (#ty) }
+            }
+        };
+
+        Ok(quote! {
+            #doc
+            #[serde(rename = #name)]
+            #ident #payload
+        })
+    }
+
+    fn unsupported(&self, what: &str) -> syn::Error {
+        syn::Error::new(
+            self.span,
+            format!("Schema contains {} which are not supported", what),
+        )
+    }
+}
+
+struct Variant<'a> {
+    name: &'a str,
+    doc: Option<&'a str>,
+    payload: Option<&'a Value>,
+}
+
+/// Returns variants if the schema describes an enum - either string enum or externally
+/// tagged `oneOf`
+fn enum_variants(schema: &Value) -> Option<Vec<Variant<'_>>> {
+    if let Some(variants) = unit_variants(schema) {
+        return Some(variants);
+    }
+
+    let variants = schema.get("oneOf")?.as_array()?;
+    let variants = variants
+        .iter()
+        .map(|variant| match unit_variants(variant) {
+            Some(variants) => Some(variants),
+            None => {
+                let (name, payload) = externally_tagged(variant)?;
+                let doc = variant.get("description").and_then(Value::as_str);
+                Some(vec![Variant {
+                    name,
+                    doc,
+                    payload: Some(payload),
+                }])
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(variants.into_iter().flatten().collect())
+}
+
+fn unit_variants(schema: &Value) -> Option<Vec<Variant<'_>>> {
+    let doc = schema.get("description").and_then(Value::as_str);
+    let values = schema.get("enum")?.as_array()?;
+    values
+        .iter()
+        .map(|value| {
+            value.as_str().map(|name| Variant {
+                name,
+                doc,
+                payload: None,
+            })
+        })
+        .collect()
+}
+
+/// Objects and enums need a named type to be represented in Rust
+fn requires_definition(schema: &Value) -> bool {
+    schema.get("properties").is_some() || enum_variants(schema).is_some()
+}
+
+/// Checks if the schema describes a struct, as opposed to a map or a type reference
+fn is_struct(schema: &Map<String, Value>) -> bool {
+    schema.contains_key("properties")
+        || schema.get("type").and_then(Value::as_str) == Some("object")
+            && !matches!(schema.get("additionalProperties"), Some(Value::Object(_)))
+}
+
+fn is_null(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// Checks if the schema is mapped to the `Option` type
+fn is_nullable(schema: &Value) -> bool {
+    let variants = schema
+        .get("anyOf")
+        .or_else(|| schema.get("oneOf"))
+        .and_then(Value::as_array);
+    if let Some(variants) = variants {
+        return matches!(variants.as_slice(), [ty, null] | [null, ty] if is_null(null) && !is_null(ty));
+    }
+
+    if let Some([single]) = schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        return is_nullable(single);
+    }
+
+    match schema
+        .get("type")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+    {
+        Some([Value::String(_), Value::String(null)]) => null == "null",
+        _ => false,
+    }
+}
+
+/// Maps well known CosmWasm definitions to `cosmwasm_std` types
+fn known_type(name: &str) -> Option<TokenStream> {
+    let sylvia = crate_module();
+
+    let ty = match name {
+        "Addr" => quote! { #sylvia ::cw_std::Addr },
+        "Binary" => quote! { #sylvia ::cw_std::Binary },
+        "Coin" => quote! { #sylvia ::cw_std::Coin },
+        "Decimal" => quote! { #sylvia ::cw_std::Decimal },
+        "Decimal256" => quote! { #sylvia ::cw_std::Decimal256 },
+        "Empty" => quote! { #sylvia ::cw_std::Empty },
+        "Timestamp" => quote! { #sylvia ::cw_std::Timestamp },
+        "Uint64" => quote! { #sylvia ::cw_std::Uint64 },
+        "Uint128" => quote! { #sylvia ::cw_std::Uint128 },
+        "Uint256" => quote! { #sylvia ::cw_std::Uint256 },
+        "Uint512" => quote! { #sylvia ::cw_std::Uint512 },
+        "BankMsg" => quote! { #sylvia ::cw_std::BankMsg },
+        "WasmMsg" => quote! { #sylvia ::cw_std::WasmMsg },
+        "CosmosMsg_for_Empty" => quote! { #sylvia ::cw_std::CosmosMsg<#sylvia ::cw_std::Empty> },
+        _ => return None,
+    };
+
+    Some(ty)
+}
+
+/// Names which cannot be raw identifiers
+const NON_RAW_IDENTS: [&str; 5] = ["self", "super", "crate", "Self", "_"];
+
+/// Returns field identifier along with the `serde` rename if the name is not a valid identifier
+fn field_ident(name: &str, span: Span) -> (Ident, Option<TokenStream>) {
+    let is_ident = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && !name.is_empty();
+
+    if NON_RAW_IDENTS.contains(&name) {
+        let ident = Ident::new(&format!("{}_", name.to_case(Case::Snake)), span);
+        return (ident, Some(quote! { #[serde(rename = #name)] }));
+    }
+
+    if is_ident && name.to_case(Case::Snake) == name {
+        let ident = match syn::parse_str::<Ident>(name) {
+            Ok(_) => Ident::new(name, span),
+            // Keywords have to be raw identifiers
+            Err(_) => Ident::new_raw(name, span),
+        };
+        return (ident, None);
+    }
+
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    let ident = Ident::new(&format!("f_{}", sanitized.to_case(Case::Snake)), span);
+    (ident, Some(quote! { #[serde(rename = #name)] }))
+}
+
+/// Returns identifier of the type generated for the `name` schema definition
+fn type_ident(name: &str, span: Span) -> syn::Result<Ident> {
+    let type_name = to_type_name(name);
+    if type_name.is_empty()
+        || type_name.starts_with(|c: char| c.is_ascii_digit())
+        || NON_RAW_IDENTS.contains(&type_name.as_str())
+    {
+        return Err(syn::Error::new(
+            span,
+            format!("`{}` cannot be converted into a type name", name),
+        ));
+    }
+
+    Ok(Ident::new(&type_name, span))
+}
+
+fn to_type_name(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_case(Case::UpperCamel))
+        .collect()
+}
@@ -6,10 +6,13 @@ use syn::spanned::Spanned;
 use syn::{parse_quote, GenericParam, Ident, ItemImpl, ItemTrait, TraitItem, Type};
 
 use crate::crate_module;
+use crate::interface_api::InterfaceApi;
 use crate::interfaces::Interfaces;
 use crate::message::{ContractEnumMessage, EnumMessage, GlueMessage, MsgVariants, StructMessage};
 use crate::multitest::{MultitestHelpers, TraitMultitestHelpers};
-use crate::parser::{ContractArgs, ContractErrorAttr, Custom, MsgType, OverrideEntryPoints};
+use crate::parser::{
+    ContractArgs, ContractErrorAttr, Custom, MsgAttr, MsgType, OverrideEntryPoints,
+};
 use crate::remote::Remote;
 use crate::variant_descs::AsVariantDescs;
 
@@ -49,6 +52,31 @@ impl<'a> TraitInput<'a> {
             );
         }
 
+        item.items
+            .iter()
+            .filter_map(|item| match item {
+                TraitItem::Method(method) => Some(method),
+                _ => None,
+            })
+            .filter(|method| {
+                method
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path.is_ident("msg"))
+                    .any(|attr| {
+                        matches!(
+                            MsgAttr::parse.parse2(attr.tokens.clone()),
+                            Ok(MsgAttr::Sudo)
+                        )
+                    })
+            })
+            .for_each(|method| {
+                emit_error!(
+                    method.sig.ident.span(), "Sudo messages are not supported on interfaces.";
+                    note = "Sudo messages have to be defined on the contract directly.";
+                );
+            });
+
         let custom = Custom::new(&item.attrs);
 
         Self {
@@ -62,7 +90,9 @@ impl<'a> TraitInput<'a> {
         let messages = self.emit_messages();
         let multitest_helpers = self.emit_helpers();
         let remote = Remote::new(&Interfaces::default()).emit();
-        let querier = MsgVariants::new(self.item.as_variants(), &self.generics).emit_querier();
+        let variants = MsgVariants::new(self.item.as_variants(), &self.generics);
+        let querier = variants.emit_querier();
+        let interface_api = InterfaceApi::new(self.item, &variants, &self.generics).emit();
 
         #[cfg(not(tarpaulin_include))]
         {
@@ -74,6 +104,8 @@ impl<'a> TraitInput<'a> {
                 #remote
 
                 #querier
+
+                #interface_api
             }
         }
     }
@@ -144,7 +176,9 @@ impl<'a> ImplInput<'a> {
 
     pub fn process(&self) -> TokenStream {
         let is_trait = self.item.trait_.is_some();
-        let multitest_helpers = if cfg!(feature = "mt") {
+        // Multitest helpers require the contract itself, which is not available in
+        // `messages_only` mode
+        let multitest_helpers = if cfg!(feature = "mt") && !self.attributes.messages_only {
             MultitestHelpers::new(
                 self.item,
                 is_trait,
@@ -190,7 +224,7 @@ impl<'a> ImplInput<'a> {
         variants: MsgVariants<'a>,
         multitest_helpers: TokenStream,
     ) -> TokenStream {
-        let messages = self.emit_messages();
+        let messages = self.emit_messages(&variants);
         let remote = Remote::new(interfaces).emit();
         let querier = variants.emit_querier();
         let querier_from_impl = interfaces.emit_querier_from_impl();
@@ -211,9 +245,20 @@ impl<'a> ImplInput<'a> {
         }
     }
 
-    fn emit_messages(&self) -> TokenStream {
+    fn emit_messages(&self, variants: &MsgVariants) -> TokenStream {
         let instantiate = self.emit_struct_msg(MsgType::Instantiate);
         let migrate = self.emit_struct_msg(MsgType::Migrate);
+        // `SudoMsg` is emitted only if declared, so it doesn't collide with the message of the
+        // overridden `sudo` entry point
+        let sudo = if variants
+            .variants()
+            .iter()
+            .any(|variant| variant.msg_type() == MsgType::Sudo)
+        {
+            self.emit_enum_msg(&Ident::new("SudoMsg", Span::mixed_site()), MsgType::Sudo)
+        } else {
+            quote! {}
+        };
         let exec_impl =
             self.emit_enum_msg(&Ident::new("ExecMsg", Span::mixed_site()), MsgType::Exec);
         let query_impl =
@@ -232,6 +277,8 @@ impl<'a> ImplInput<'a> {
 
                 #migrate
 
+                #sudo
+
                 #exec
 
                 #query
@@ -240,8 +287,14 @@ impl<'a> ImplInput<'a> {
     }
 
     fn emit_struct_msg(&self, msg_ty: MsgType) -> TokenStream {
-        StructMessage::new(self.item, msg_ty, &self.generics, &self.custom)
-            .map_or(quote! {}, |msg| msg.emit())
+        StructMessage::new(
+            self.item,
+            msg_ty,
+            &self.generics,
+            &self.custom,
+            self.attributes,
+        )
+        .map_or(quote! {}, |msg| msg.emit())
     }
 
     fn emit_enum_msg(&self, name: &Ident, msg_ty: MsgType) -> TokenStream {
@@ -252,6 +305,7 @@ impl<'a> ImplInput<'a> {
             &self.generics,
             &self.error,
             &self.custom,
+            self.attributes,
         )
         .emit()
     }
@@ -264,6 +318,7 @@ impl<'a> ImplInput<'a> {
             &self.error,
             &self.custom,
             &self.interfaces,
+            self.attributes,
         )
         .emit()
     }
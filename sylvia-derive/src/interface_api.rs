@@ -0,0 +1,148 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use sha2::{Digest, Sha256};
+use syn::fold::Fold;
+use syn::{GenericParam, ItemTrait, Path, Type, TypePath};
+
+use crate::crate_module;
+use crate::message::{MsgVariant, MsgVariants};
+use crate::parser::MsgType;
+
+/// Number of bytes of the signatures digest used as an interface id
+const INTERFACE_ID_LEN: usize = 8;
+
+/// Static description of the interface - its name, id and supported messages
+pub struct InterfaceApi<'a> {
+    source: &'a ItemTrait,
+    variants: &'a MsgVariants<'a>,
+    is_generic: bool,
+}
+
+impl<'a> InterfaceApi<'a> {
+    pub fn new(
+        source: &'a ItemTrait,
+        variants: &'a MsgVariants<'a>,
+        generics: &[&'a GenericParam],
+    ) -> Self {
+        Self {
+            source,
+            variants,
+            is_generic: !generics.is_empty(),
+        }
+    }
+
+    pub fn emit(&self) -> TokenStream {
+        let sylvia = crate_module();
+        let name = self.source.ident.to_string();
+        let id = self.interface_id();
+        let exec_messages = self.messages(MsgType::Exec);
+        let query_messages = self.messages(MsgType::Query);
+
+        // Generic interfaces have no concrete messages to generate schema for. Users of such
+        // interfaces can still build `InterfaceSchema` providing the messages types by hand.
+        let schema = if self.is_generic {
+            quote! {}
+        } else {
+            quote! {
+                #[cfg(not(target_arch = "wasm32"))]
+                impl InterfaceApi {
+                    pub fn schema() -> #sylvia ::schema::InterfaceSchema {
+                        #sylvia ::schema::InterfaceSchema::new::<Self, ExecMsg, QueryMsg>()
+                    }
+                }
+            }
+        };
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                pub struct InterfaceApi;
+
+                impl #sylvia ::types::Interface for InterfaceApi {
+                    const NAME: &'static str = #name;
+                    const ID: &'static str = #id;
+                    const EXEC_MESSAGES: &'static [&'static str] = &[#(#exec_messages,)*];
+                    const QUERY_MESSAGES: &'static [&'static str] = &[#(#query_messages,)*];
+                }
+
+                #schema
+            }
+        }
+    }
+
+    fn messages(&self, msg_ty: MsgType) -> Vec<String> {
+        let mut msgs: Vec<_> = self
+            .variants
+            .variants()
+            .iter()
+            .filter(|variant| variant.msg_type() == msg_ty)
+            .map(|variant| variant.name().to_string().to_case(Case::Snake))
+            .collect();
+        msgs.sort();
+        msgs
+    }
+
+    /// Id is derived only from the messages signatures, so two interfaces exposing the same
+    /// messages are considered compatible regardless of the trait name.
+    fn interface_id(&self) -> String {
+        let mut signatures: Vec<_> = self
+            .variants
+            .variants()
+            .iter()
+            .filter(|variant| matches!(variant.msg_type(), MsgType::Exec | MsgType::Query))
+            .map(signature)
+            .collect();
+        signatures.sort();
+
+        let digest = Sha256::digest(signatures.join(";").as_bytes());
+        digest[..INTERFACE_ID_LEN]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// Canonical representation of the message, e.g. `exec transfer(recipient:String,amount:Uint128)`
+fn signature(variant: &MsgVariant) -> String {
+    let kind = match variant.msg_type() {
+        MsgType::Exec => "exec",
+        _ => "query",
+    };
+    let name = variant.name().to_string().to_case(Case::Snake);
+    let fields: Vec<_> = variant
+        .fields()
+        .iter()
+        .map(|field| format!("{}:{}", field.name(), canonical_type(field.ty())))
+        .collect();
+    let resp = match syn::parse2::<Type>(variant.return_type().clone()) {
+        Ok(ty) => canonical_type(&ty),
+        Err(_) => String::new(),
+    };
+
+    format!("{} {}({}){}", kind, name, fields.join(","), resp)
+}
+
+/// Type with paths shortened to their last segment and without whitespaces, so
+/// `cosmwasm_std::Uint128` and `Uint128` are the same type regardless of the tokens formatting.
+fn canonical_type(ty: &Type) -> String {
+    let ty = StripPaths.fold_type(ty.clone());
+    quote! { #ty }.to_string().split_whitespace().collect()
+}
+
+struct StripPaths;
+
+impl Fold for StripPaths {
+    fn fold_type_path(&mut self, ty: TypePath) -> TypePath {
+        if ty.qself.is_some() {
+            return syn::fold::fold_type_path(self, ty);
+        }
+
+        let segment = ty.path.segments.last().cloned();
+        let path = Path {
+            leading_colon: None,
+            segments: segment.into_iter().collect(),
+        };
+        syn::fold::fold_type_path(self, TypePath { qself: None, path })
+    }
+}
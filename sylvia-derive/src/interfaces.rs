@@ -122,24 +122,73 @@ impl Interfaces {
             .collect()
     }
 
-    pub fn emit_deserialization_attempts(&self, msg_name: &Ident) -> Vec<TokenStream> {
+    /// Pairs of checks if the received message belongs to the interface with the glue variants
+    /// wrapping it
+    pub fn emit_deserialization_arms(
+        &self,
+        msg_ty: &MsgType,
+        msg_name: &Ident,
+    ) -> Vec<(TokenStream, TokenStream)> {
         self.interfaces
             .iter()
             .map(|interface| {
                 let ContractMessageAttr {
-                    module, variant, ..
+                    module,
+                    exec_generic_params,
+                    query_generic_params,
+                    variant,
+                    ..
                 } = interface;
+
+                let generics = match msg_ty {
+                    MsgType::Exec => exec_generic_params.as_slice(),
+                    MsgType::Query => query_generic_params.as_slice(),
+                    _ => &[],
+                };
+                let generics = if generics.is_empty() {
+                    quote! {}
+                } else {
+                    quote! { ::<#(#generics,)*> }
+                };
+
                 let enum_name = Self::merge_module_with_name(interface, msg_name);
 
-                quote! {
-                    let msgs = &#module :: #enum_name ::messages();
-                    if msgs.into_iter().any(|msg| msg == &recv_msg_name) {
-                        match val.deserialize_into() {
-                            Ok(msg) => return Ok(Self:: #variant (msg)),
-                            Err(err) => return Err(D::Error::custom(err)).map(Self:: #variant),
-                        };
-                    }
-                }
+                (
+                    quote! { #module :: #enum_name #generics ::is_message(name) },
+                    quote! { #variant },
+                )
+            })
+            .collect()
+    }
+
+    /// Pairs of glue variants with types of the interface messages they wrap
+    pub fn emit_variants_types(
+        &self,
+        msg_ty: &MsgType,
+        msg_name: &Ident,
+    ) -> Vec<(TokenStream, TokenStream)> {
+        self.interfaces
+            .iter()
+            .map(|interface| {
+                let ContractMessageAttr {
+                    module,
+                    exec_generic_params,
+                    query_generic_params,
+                    variant,
+                    ..
+                } = interface;
+
+                let generics = match msg_ty {
+                    MsgType::Exec => exec_generic_params.as_slice(),
+                    MsgType::Query => query_generic_params.as_slice(),
+                    _ => &[],
+                };
+
+                let enum_name = Self::merge_module_with_name(interface, msg_name);
+                (
+                    quote! { #variant },
+                    quote! { #module :: #enum_name<#(#generics,)*> },
+                )
             })
             .collect()
     }
@@ -155,6 +204,16 @@ impl Interfaces {
             .collect()
     }
 
+    pub fn emit_interfaces_info(&self) -> Vec<TokenStream> {
+        let sylvia = crate_module();
+
+        self.as_modules()
+            .map(
+                |module| quote! { #sylvia ::types::InterfaceInfo::new::<#module ::InterfaceApi>() },
+            )
+            .collect()
+    }
+
     pub fn as_modules(&self) -> impl Iterator<Item = &Path> {
         self.interfaces.iter().map(|interface| &interface.module)
     }
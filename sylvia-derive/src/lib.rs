@@ -7,7 +7,11 @@ use syn::fold::Fold;
 use syn::{parse2, parse_quote, ItemImpl, ItemTrait, Path};
 
 pub(crate) mod check_generics;
+mod cli;
+mod fuzz;
+mod import_schema;
 mod input;
+mod interface_api;
 mod interfaces;
 mod message;
 mod multitest;
@@ -205,6 +209,10 @@ fn interface_impl(_attr: TokenStream2, item: TokenStream2) -> TokenStream2 {
 /// `contract` attribute takes optional parameters:
 /// * `module` - defines module name, where all generated messages would be encapsulated; no
 /// additional module would be created if not provided
+/// * `messages_only` - generates only messages, `Remote` and queriers, dropping the impl block
+/// and dispatching. Intended for lightweight `-types` builds of the contract crate, typically
+/// enabled with `#[cfg_attr(feature = "types", contract(messages_only))]`
+/// * `slim` - derives `Debug` and `PartialEq` of generated messages only on non-wasm targets
 ///
 /// ## Attributes
 ///
@@ -237,6 +245,13 @@ fn contract_impl(attr: TokenStream2, item: TokenStream2) -> TokenStream2 {
         let input: ItemImpl = parse2(item)?;
 
         let expanded = ImplInput::new(&attrs, &input).process();
+
+        // In `messages_only` mode the impl block is dropped, so neither the contract type nor
+        // handlers bodies are compiled
+        if attrs.messages_only {
+            return Ok(expanded);
+        }
+
         let input = StripInput.fold_item_impl(input);
 
         Ok(quote! {
@@ -249,6 +264,35 @@ fn contract_impl(attr: TokenStream2, item: TokenStream2) -> TokenStream2 {
     inner(attr, item).unwrap_or_else(syn::Error::into_compile_error)
 }
 
+/// Macro generating interface bindings from the API JSON generated by `cosmwasm_schema`.
+///
+/// Allows to communicate with contracts for which no sylvia interface exists, for example
+/// contracts not written in sylvia. Path is relative to the crate manifest directory.
+///
+/// ## Example usage
+/// ```ignore
+/// pub mod cw20 {
+///     sylvia::import_schema!("schema/cw20-base.json");
+/// }
+/// ```
+///
+/// Schema `contract_name` becomes the name of the generated interface trait, so for the example
+/// above the `Cw20Base` trait is generated, along with everything `#[interface]` would
+/// generate for it: `ExecMsg`/`QueryMsg`, `Querier`, `BoundQuerier`, `Remote`, `InterfaceApi`
+/// and multitest helpers. Types used by the messages are generated as well, except for
+/// the well known CosmWasm types which are mapped to `cosmwasm_std` ones.
+///
+/// The trait can be implemented with `#[contract]` to provide a mock of the imported contract
+/// for multitest.
+#[cfg(not(tarpaulin_include))]
+#[proc_macro_error]
+#[proc_macro]
+pub fn import_schema(input: TokenStream) -> TokenStream {
+    import_schema::ImportSchema::process(input.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 #[cfg(not(tarpaulin_include))]
 #[proc_macro_error]
 #[proc_macro_attribute]
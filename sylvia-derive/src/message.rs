@@ -1,9 +1,11 @@
 use crate::check_generics::CheckGenerics;
+use crate::cli::CliMsg;
 use crate::crate_module;
+use crate::fuzz::Generate;
 use crate::interfaces::Interfaces;
 use crate::parser::{
-    parse_associated_custom_type, parse_struct_message, ContractErrorAttr, ContractMessageAttr,
-    Custom, MsgAttr, MsgType, OverrideEntryPoint, OverrideEntryPoints,
+    parse_associated_custom_type, parse_struct_message, ContractArgs, ContractErrorAttr,
+    ContractMessageAttr, Custom, MsgAttr, MsgType, OverrideEntryPoint, OverrideEntryPoints,
 };
 use crate::strip_generics::StripGenerics;
 use crate::utils::{extract_return_type, filter_wheres, process_fields};
@@ -33,6 +35,8 @@ pub struct StructMessage<'a> {
     result: &'a ReturnType,
     msg_attr: MsgAttr,
     custom: &'a Custom<'a>,
+    messages_only: bool,
+    slim: bool,
 }
 
 impl<'a> StructMessage<'a> {
@@ -42,6 +46,7 @@ impl<'a> StructMessage<'a> {
         ty: MsgType,
         generics: &'a [&'a GenericParam],
         custom: &'a Custom,
+        args: &ContractArgs,
     ) -> Option<StructMessage<'a>> {
         let mut generics_checker = CheckGenerics::new(generics);
 
@@ -68,6 +73,8 @@ impl<'a> StructMessage<'a> {
             result: &method.sig.output,
             msg_attr,
             custom,
+            messages_only: args.messages_only,
+            slim: args.slim,
         })
     }
 
@@ -98,6 +105,8 @@ impl<'a> StructMessage<'a> {
             result,
             msg_attr,
             custom,
+            messages_only,
+            slim,
         } = self;
 
         let where_clause = if !wheres.is_empty() {
@@ -112,12 +121,14 @@ impl<'a> StructMessage<'a> {
             .msg_type()
             .emit_ctx_type(&custom.query_or_default());
         let fields_names: Vec<_> = fields.iter().map(MsgField::name).collect();
+        let cli_fields = fields;
         let parameters = fields.iter().map(|field| {
             let name = field.name;
             let ty = field.ty;
             quote! { #name : #ty}
         });
         let fields = fields.iter().map(MsgField::emit);
+        let optional_derives = emit_optional_derives(false, *slim);
 
         let generics = if generics.is_empty() {
             quote! {}
@@ -126,6 +137,8 @@ impl<'a> StructMessage<'a> {
                 <#(#generics,)*>
             }
         };
+        let schema_stub = emit_schema_stub(name, &generics, &where_clause);
+        let cli = CliMsg::new(name, &generics, wheres).emit_struct(cli_fields);
 
         let unused_generics = if unused_generics.is_empty() {
             quote! {}
@@ -135,28 +148,44 @@ impl<'a> StructMessage<'a> {
             }
         };
 
+        #[cfg(not(tarpaulin_include))]
+        let dispatch = if *messages_only {
+            quote! {}
+        } else {
+            quote! {
+                impl #generics #name #generics #where_clause {
+                    pub fn dispatch #unused_generics(self, contract: &#contract_type, ctx: #ctx_type)
+                        #result #full_where
+                    {
+                        let Self { #(#fields_names,)* } = self;
+                        contract.#function_name(Into::into(ctx), #(#fields_names,)*).map_err(Into::into)
+                    }
+                }
+            }
+        };
+
         #[cfg(not(tarpaulin_include))]
         {
             quote! {
                 #[allow(clippy::derive_partial_eq_without_eq)]
-                #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema)]
+                #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone)]
+                #optional_derives
                 #[serde(rename_all="snake_case")]
                 pub struct #name #generics #where_clause {
                     #(pub #fields,)*
                 }
 
+                #schema_stub
+
                 impl #generics #name #generics #where_clause {
                     pub fn new(#(#parameters,)*) -> Self {
                         Self { #(#fields_names,)* }
                     }
-
-                    pub fn dispatch #unused_generics(self, contract: &#contract_type, ctx: #ctx_type)
-                        #result #full_where
-                    {
-                        let Self { #(#fields_names,)* } = self;
-                        contract.#function_name(Into::into(ctx), #(#fields_names,)*).map_err(Into::into)
-                    }
                 }
+
+                #dispatch
+
+                #cli
             }
         }
     }
@@ -269,7 +298,9 @@ impl<'a> EnumMessage<'a> {
             .collect();
         msgs.sort();
         let msgs_cnt = msgs.len();
+        let is_message = emit_is_message(&msgs);
         let variants_constructors = variants.iter().map(MsgVariant::emit_variants_constructors);
+        let msg_variants = variants;
         let variants = variants.iter().map(MsgVariant::emit);
         let where_clause = if !wheres.is_empty() {
             quote! {
@@ -296,12 +327,16 @@ impl<'a> EnumMessage<'a> {
 
         let unique_enum_name = Ident::new(&format!("{}{}", trait_name, name), name.span());
 
+        let optional_derives = emit_optional_derives(false, false);
+        let query_optional_derives = emit_optional_derives(true, false);
+
         #[cfg(not(tarpaulin_include))]
         let enum_declaration = match name.to_string().as_str() {
             "QueryMsg" => {
                 quote! {
                     #[allow(clippy::derive_partial_eq_without_eq)]
-                    #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema, cosmwasm_schema::QueryResponses)]
+                    #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone)]
+                    #query_optional_derives
                     #[serde(rename_all="snake_case")]
                     pub enum #unique_enum_name #generics #where_clause {
                         #(#variants,)*
@@ -312,7 +347,8 @@ impl<'a> EnumMessage<'a> {
             _ => {
                 quote! {
                     #[allow(clippy::derive_partial_eq_without_eq)]
-                    #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema)]
+                    #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone)]
+                    #optional_derives
                     #[serde(rename_all="snake_case")]
                     pub enum #unique_enum_name #generics #where_clause {
                         #(#variants,)*
@@ -321,12 +357,17 @@ impl<'a> EnumMessage<'a> {
                 }
             }
         };
+        let schema_stub = emit_schema_stub(&unique_enum_name, &generics, &where_clause);
+        let cli = CliMsg::new(&unique_enum_name, &generics, wheres).emit_enum(msg_variants);
+        let generate = Generate::new(&unique_enum_name, &generics, wheres).emit_enum(msg_variants);
 
         #[cfg(not(tarpaulin_include))]
         {
             quote! {
                 #enum_declaration
 
+                #schema_stub
+
                 impl #generics #unique_enum_name #generics #where_clause {
                     pub fn dispatch<C: #trait_name #all_generics, #(#unused_generics,)*>(self, contract: &C, ctx: #ctx_type)
                         -> #dispatch_type #full_where
@@ -340,8 +381,15 @@ impl<'a> EnumMessage<'a> {
                     pub const fn messages() -> [&'static str; #msgs_cnt] {
                         [#(#msgs,)*]
                     }
+
+                    #is_message
+
                     #(#variants_constructors)*
                 }
+
+                #cli
+
+                #generate
             }
         }
     }
@@ -355,6 +403,8 @@ pub struct ContractEnumMessage<'a> {
     contract: &'a Type,
     error: &'a Type,
     custom: &'a Custom<'a>,
+    messages_only: bool,
+    slim: bool,
 }
 
 impl<'a> ContractEnumMessage<'a> {
@@ -365,6 +415,7 @@ impl<'a> ContractEnumMessage<'a> {
         generics: &'a [&'a GenericParam],
         error: &'a Type,
         custom: &'a Custom,
+        args: &ContractArgs,
     ) -> Self {
         let mut generics_checker = CheckGenerics::new(generics);
         let variants: Vec<_> = source
@@ -398,6 +449,8 @@ impl<'a> ContractEnumMessage<'a> {
             contract: &source.self_ty,
             error,
             custom,
+            messages_only: args.messages_only,
+            slim: args.slim,
         }
     }
 
@@ -411,6 +464,8 @@ impl<'a> ContractEnumMessage<'a> {
             contract,
             error,
             custom,
+            messages_only,
+            slim,
         } = self;
 
         let match_arms = variants
@@ -422,42 +477,50 @@ impl<'a> ContractEnumMessage<'a> {
             .collect();
         msgs.sort();
         let msgs_cnt = msgs.len();
+        let is_message = emit_is_message(&msgs);
         let variants_constructors = variants.iter().map(MsgVariant::emit_variants_constructors);
+        let cli = CliMsg::new(name, &quote! {}, &[]).emit_enum(variants);
+        let generate = Generate::new(name, &quote! {}, &[]).emit_enum(variants);
         let variants = variants.iter().map(MsgVariant::emit);
 
         let ctx_type = msg_ty.emit_ctx_type(&custom.query_or_default());
         let contract = StripGenerics.fold_type((*contract).clone());
         let ret_type = msg_ty.emit_result_type(&custom.msg_or_default(), error);
 
+        let optional_derives = emit_optional_derives(false, *slim);
+        let query_optional_derives = emit_optional_derives(true, *slim);
+
         #[cfg(not(tarpaulin_include))]
         let enum_declaration = match name.to_string().as_str() {
             "QueryMsg" => {
                 quote! {
-                        #[allow(clippy::derive_partial_eq_without_eq)]
-                        #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema, cosmwasm_schema::QueryResponses)]
-                        #[serde(rename_all="snake_case")]
-                        pub enum #name {
-                            #(#variants,)*
-                        }
+                    #[allow(clippy::derive_partial_eq_without_eq)]
+                    #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone)]
+                    #query_optional_derives
+                    #[serde(rename_all="snake_case")]
+                    pub enum #name {
+                        #(#variants,)*
+                    }
                 }
             }
             _ => {
                 quote! {
-                        #[allow(clippy::derive_partial_eq_without_eq)]
-                        #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema)]
-                        #[serde(rename_all="snake_case")]
-                        pub enum #name {
-                            #(#variants,)*
+                    #[allow(clippy::derive_partial_eq_without_eq)]
+                    #[derive(#sylvia ::serde::Serialize, #sylvia ::serde::Deserialize, Clone)]
+                    #optional_derives
+                    #[serde(rename_all="snake_case")]
+                    pub enum #name {
+                        #(#variants,)*
                     }
                 }
             }
         };
 
         #[cfg(not(tarpaulin_include))]
-        {
+        let dispatch = if *messages_only {
+            quote! {}
+        } else {
             quote! {
-                #enum_declaration
-
                 impl #name {
                     pub fn dispatch(self, contract: &#contract, ctx: #ctx_type) -> #ret_type {
                         use #name::*;
@@ -466,17 +529,112 @@ impl<'a> ContractEnumMessage<'a> {
                             #(#match_arms,)*
                         }
                     }
+                }
+            }
+        };
+
+        let schema_stub = emit_schema_stub(name, &quote! {}, &quote! {});
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                #enum_declaration
+
+                #schema_stub
+
+                impl #name {
                     pub const fn messages() -> [&'static str; #msgs_cnt] {
                         [#(#msgs,)*]
                     }
 
+                    #is_message
+
                     #(#variants_constructors)*
                 }
+
+                #dispatch
+
+                #cli
+
+                #generate
+            }
+        }
+    }
+}
+
+/// Emits derives not needed by the contract itself.
+///
+/// Schema is generated only off-chain, so `JsonSchema` and `QueryResponses` are not derived on
+/// wasm. Contracts marked `#[contract(slim)]` skip `Debug` and `PartialEq` on wasm as well.
+fn emit_optional_derives(query_responses: bool, slim: bool) -> TokenStream {
+    let sylvia = crate_module();
+
+    let schema = if query_responses {
+        quote! { #sylvia ::schemars::JsonSchema, cosmwasm_schema::QueryResponses }
+    } else {
+        quote! { #sylvia ::schemars::JsonSchema }
+    };
+
+    let debug = if slim {
+        quote! { #[cfg_attr(not(target_arch = "wasm32"), derive(Debug, PartialEq))] }
+    } else {
+        quote! { #[derive(Debug, PartialEq)] }
+    };
+
+    quote! {
+        #debug
+        #[cfg_attr(not(target_arch = "wasm32"), derive(#schema))]
+    }
+}
+
+/// Emits `JsonSchema` implementation used on wasm instead of the derived one.
+///
+/// Generated messages are often embedded in `#[cw_serde]` types requiring `JsonSchema` on all
+/// targets. The schema is never generated on chain, so a stub keeps them compiling without
+/// the cost of the derived implementation.
+fn emit_schema_stub(
+    name: &Ident,
+    generics: &TokenStream,
+    where_clause: &TokenStream,
+) -> TokenStream {
+    let sylvia = crate_module();
+
+    #[cfg(not(tarpaulin_include))]
+    {
+        quote! {
+            #[cfg(target_arch = "wasm32")]
+            impl #generics #sylvia ::schemars::JsonSchema for #name #generics #where_clause {
+                fn schema_name() -> String {
+                    stringify!(#name).to_owned()
+                }
+
+                fn json_schema(_: &mut #sylvia ::schemars::gen::SchemaGenerator) -> #sylvia ::schemars::schema::Schema {
+                    #sylvia ::schemars::schema::Schema::Bool(true)
+                }
             }
         }
     }
 }
 
+/// Emits `is_message` function checking if the name is one of sorted `msgs`. Used to pick
+/// a glue message variant while deserializing.
+fn emit_is_message(msgs: &[String]) -> TokenStream {
+    let body = if msgs.is_empty() {
+        quote! {
+            let _ = name;
+            false
+        }
+    } else {
+        quote! { matches!(name, #(#msgs)|*) }
+    };
+
+    quote! {
+        pub fn is_message(name: &str) -> bool {
+            #body
+        }
+    }
+}
+
 /// Representation of whole message variant
 pub struct MsgVariant<'a> {
     name: Ident,
@@ -535,7 +693,7 @@ impl<'a> MsgVariant<'a> {
             #[cfg(not(tarpaulin_include))]
             {
                 quote! {
-                    #[returns(#return_type)]
+                    #[cfg_attr(not(target_arch = "wasm32"), returns(#return_type))]
                     #name {
                         #(#fields,)*
                     }
@@ -581,7 +739,7 @@ impl<'a> MsgVariant<'a> {
 
         #[cfg(not(tarpaulin_include))]
         match msg_type {
-            Exec => quote! {
+            Exec | Sudo => quote! {
                 #name {
                     #(#fields,)*
                 } => contract.#function_name(Into::into(ctx), #(#args),*).map_err(Into::into)
@@ -591,8 +749,8 @@ impl<'a> MsgVariant<'a> {
                     #(#fields,)*
                 } => #sylvia ::cw_std::to_binary(&contract.#function_name(Into::into(ctx), #(#args),*)?).map_err(Into::into)
             },
-            Instantiate | Migrate | Reply | Sudo => {
-                emit_error!(name.span(), "Instantiation, Reply, Migrate and Sudo messages not supported on traits, they should be defined on contracts directly");
+            Instantiate | Migrate | Reply => {
+                emit_error!(name.span(), "Instantiation, Reply and Migrate messages not supported on traits, they should be defined on contracts directly");
                 quote! {}
             }
         }
@@ -646,6 +804,22 @@ impl<'a> MsgVariant<'a> {
         }
     }
 
+    pub fn name(&self) -> &Ident {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[MsgField<'a>] {
+        &self.fields
+    }
+
+    pub fn msg_type(&self) -> MsgType {
+        self.msg_type
+    }
+
+    pub fn return_type(&self) -> &TokenStream {
+        &self.return_type
+    }
+
     pub fn emit_querier_declaration(&self) -> TokenStream {
         let sylvia = crate_module();
         let Self {
@@ -694,6 +868,10 @@ impl<'a> MsgVariants<'a> {
         Self(variants)
     }
 
+    pub fn variants(&self) -> &[MsgVariant<'a>] {
+        &self.0
+    }
+
     pub fn emit_querier(&self) -> TokenStream {
         let sylvia = crate_module();
         let variants = &self.0;
@@ -728,6 +906,14 @@ impl<'a> MsgVariants<'a> {
                     pub fn borrowed(contract: &'a #sylvia ::cw_std::Addr, querier: &'a #sylvia ::cw_std::QuerierWrapper<'a, C>) -> Self {
                         Self {contract, querier}
                     }
+
+                    pub fn sylvia_info(&self) -> Result<#sylvia ::types::SylviaInfoResponse, #sylvia ::cw_std::StdError> {
+                        #sylvia ::types::SylviaQueryMsg::query(self.querier, self.contract)
+                    }
+
+                    pub fn supports<I: #sylvia ::types::Interface>(&self) -> Result<bool, #sylvia ::cw_std::StdError> {
+                        self.sylvia_info().map(|info| info.supports::<I>())
+                    }
                 }
 
                 impl <'a, C: #sylvia ::cw_std::CustomQuery> Querier for BoundQuerier<'a, C> {
@@ -842,6 +1028,10 @@ impl<'a> MsgField<'a> {
     pub fn name(&self) -> &'a Ident {
         self.name
     }
+
+    pub fn ty(&self) -> &'a Type {
+        self.ty
+    }
 }
 
 /// Glue message is the message composing Exec/Query messages from several traits
@@ -853,6 +1043,8 @@ pub struct GlueMessage<'a> {
     error: &'a Type,
     custom: &'a Custom<'a>,
     interfaces: &'a Interfaces,
+    messages_only: bool,
+    slim: bool,
 }
 
 impl<'a> GlueMessage<'a> {
@@ -863,6 +1055,7 @@ impl<'a> GlueMessage<'a> {
         error: &'a Type,
         custom: &'a Custom,
         interfaces: &'a Interfaces,
+        args: &ContractArgs,
     ) -> Self {
         GlueMessage {
             name,
@@ -871,6 +1064,8 @@ impl<'a> GlueMessage<'a> {
             error,
             custom,
             interfaces,
+            messages_only: args.messages_only,
+            slim: args.slim,
         }
     }
 
@@ -884,17 +1079,27 @@ impl<'a> GlueMessage<'a> {
             error,
             custom,
             interfaces,
+            messages_only,
+            slim,
         } = self;
         let contract = StripGenerics.fold_type((*contract).clone());
         let contract_name = Ident::new(&format!("Contract{}", name), name.span());
 
-        let variants = interfaces.emit_glue_message_variants(msg_ty, name);
+        let mut variants = interfaces.emit_glue_message_variants(msg_ty, name);
 
         let msg_name = quote! {#contract ( #name)};
         let mut messages_call_on_all_variants: Vec<TokenStream> =
             interfaces.emit_messages_call(name);
         messages_call_on_all_variants.push(quote! {&#name :: messages()});
 
+        // Every contract query message additionally contains reserved `sylvia_info` query
+        let is_query = *msg_ty == MsgType::Query;
+        if is_query {
+            variants.push(quote! { Sylvia(#sylvia ::types::SylviaQueryMsg) });
+            messages_call_on_all_variants
+                .push(quote! {&#sylvia ::types::SylviaQueryMsg::messages()});
+        }
+
         let variants_cnt = messages_call_on_all_variants.len();
 
         let dispatch_arms = interfaces.interfaces().iter().map(|interface| {
@@ -926,24 +1131,56 @@ impl<'a> GlueMessage<'a> {
 
         let dispatch_arm = quote! {#contract_name :: #contract (msg) =>msg.dispatch(contract, ctx)};
 
-        let interfaces_deserialization_attempts = interfaces.emit_deserialization_attempts(name);
+        let sylvia_dispatch_arm = if is_query {
+            let interfaces_info = interfaces.emit_interfaces_info();
 
-        #[cfg(not(tarpaulin_include))]
-        let contract_deserialization_attempt = quote! {
-            let msgs = &#name :: messages();
-            if msgs.into_iter().any(|msg| msg == &recv_msg_name) {
-                match val.deserialize_into() {
-                    Ok(msg) => return Ok(Self:: #contract (msg)),
-                    Err(err) => return Err(D::Error::custom(err)).map(Self:: #contract)
-                };
+            quote! {
+                #contract_name :: Sylvia(msg) => {
+                    let info = #sylvia ::types::SylviaInfoResponse {
+                        contract: env!("CARGO_PKG_NAME").to_owned(),
+                        version: env!("CARGO_PKG_VERSION").to_owned(),
+                        interfaces: vec![#(#interfaces_info),*],
+                    };
+                    msg.dispatch(info).map_err(Into::into)
+                },
             }
+        } else {
+            quote! {}
         };
 
+        let mut variants_types = interfaces.emit_variants_types(msg_ty, name);
+        if is_query {
+            variants_types.push((
+                quote! { Sylvia },
+                quote! { #sylvia ::types::SylviaQueryMsg },
+            ));
+        }
+        variants_types.push((quote! { #contract }, quote! { #name }));
+        let cli = CliMsg::new(&contract_name, &quote! {}, &[]).emit_glue(&variants_types);
+        let generate = Generate::new(&contract_name, &quote! {}, &[]).emit_glue(&variants_types);
+
+        let mut deserialization_arms = interfaces.emit_deserialization_arms(msg_ty, name);
+        if is_query {
+            deserialization_arms.push((
+                quote! { #sylvia ::types::SylviaQueryMsg::is_message(name) },
+                quote! { Sylvia },
+            ));
+        }
+        deserialization_arms.push((quote! { #name :: is_message(name) }, quote! { #contract }));
+        let (is_message_checks, glue_variants): (Vec<_>, Vec<_>) =
+            deserialization_arms.into_iter().unzip();
+
         let ctx_type = msg_ty.emit_ctx_type(&custom.query_or_default());
         let ret_type = msg_ty.emit_result_type(&custom.msg_or_default(), error);
 
+        let optional_derives = emit_optional_derives(false, *slim);
+        let schema_stub = emit_schema_stub(&contract_name, &quote! {}, &quote! {});
+
         let mut response_schemas_calls = interfaces.emit_response_schemas_calls(name);
         response_schemas_calls.push(quote! {#name :: response_schemas_impl()});
+        response_schemas_calls.push(quote! {
+            <#sylvia ::types::SylviaQueryMsg as cosmwasm_schema::QueryResponses>::response_schemas_impl()
+        });
 
         let response_schemas = match name.to_string().as_str() {
             "QueryMsg" => {
@@ -966,71 +1203,88 @@ impl<'a> GlueMessage<'a> {
         };
 
         #[cfg(not(tarpaulin_include))]
-        {
+        let dispatch = if *messages_only {
+            quote! {}
+        } else {
             quote! {
-                #[allow(clippy::derive_partial_eq_without_eq)]
-                #[derive(#sylvia ::serde::Serialize, Clone, Debug, PartialEq, #sylvia ::schemars::JsonSchema)]
-                #[serde(rename_all="snake_case", untagged)]
-                pub enum #contract_name {
-                    #(#variants,)*
-                    #msg_name
-                }
-
                 impl #contract_name {
                     pub fn dispatch(
                         self,
                         contract: &#contract,
                         ctx: #ctx_type,
                     ) -> #ret_type {
-                        const _: () = {
-                            let msgs: [&[&str]; #variants_cnt] = [#(#messages_call_on_all_variants),*];
-                            #sylvia ::utils::assert_no_intersection(msgs);
-                        };
-
                         match self {
                             #(#dispatch_arms,)*
+                            #sylvia_dispatch_arm
                             #dispatch_arm
                         }
                     }
                 }
+            }
+        };
+
+        #[cfg(not(tarpaulin_include))]
+        {
+            quote! {
+                #[allow(clippy::derive_partial_eq_without_eq)]
+                #[derive(#sylvia ::serde::Serialize, Clone)]
+                #optional_derives
+                #[serde(rename_all="snake_case", untagged)]
+                pub enum #contract_name {
+                    #(#variants,)*
+                    #msg_name
+                }
+
+                #schema_stub
+
+                const _: () = {
+                    let msgs: [&[&str]; #variants_cnt] = [#(#messages_call_on_all_variants),*];
+                    #sylvia ::utils::assert_no_intersection(msgs);
+                };
+
+                #dispatch
+
+                impl #contract_name {
+                    /// Names of all the messages, including the interfaces ones
+                    pub fn messages() -> Vec<&'static str> {
+                        let msgs: [&[&str]; #variants_cnt] = [#(#messages_call_on_all_variants),*];
+                        msgs.into_iter().flatten().copied().collect()
+                    }
+                }
 
                 #response_schemas
 
-                impl<'de> serde::Deserialize<'de> for #contract_name {
-                    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                #cli
+
+                #generate
+
+                impl #sylvia ::de::GlueMessage for #contract_name {
+                    fn messages() -> Vec<&'static str> {
+                        Self::messages()
+                    }
+
+                    fn is_message(name: &str) -> bool {
+                        #(#is_message_checks)||*
+                    }
+
+                    fn deserialize_payload<'de, D>(name: &str, payload: D) -> Result<Self, D::Error>
                         where D: serde::Deserializer<'de>,
                     {
                         use serde::de::Error;
 
-                        let val = #sylvia ::serde_value::Value::deserialize(deserializer)?;
-                        let map = match &val {
-                            #sylvia ::serde_value::Value::Map(map) => map,
-                            _ => return Err(D::Error::custom("Wrong message format!"))
-                        };
-                        if map.len() != 1 {
-                            return Err(D::Error::custom(format!("Expected exactly one message. Received {}", map.len())))
-                        }
-
-                        // Due to earlier size check of map this unwrap is safe
-                        let recv_msg_name = map.into_iter().next().unwrap();
-
-                        if let #sylvia ::serde_value::Value::String(recv_msg_name) = &recv_msg_name .0 {
-                            #(#interfaces_deserialization_attempts)*
-                            #contract_deserialization_attempt
+                        // Only the payload of the picked variant is deserialized, straight from the input
+                        match name {
+                            #(name if #is_message_checks => #sylvia ::de::deserialize_tagged(name, payload).map(#contract_name :: #glue_variants),)*
+                            name => Err(D::Error::custom(#sylvia ::de::MessageError::unsupported(name, Self::messages()))),
                         }
+                    }
+                }
 
-                        let msgs: [&[&str]; #variants_cnt] = [#(#messages_call_on_all_variants),*];
-                        let mut err_msg = msgs.into_iter().flatten().fold(
-                            // It might be better to forward the error or serialization, but we just
-                            // deserialized it from JSON, not reason to expect failure here.
-                            format!(
-                                "Unsupported message received: {}. Messages supported by this contract: ",
-                                #sylvia ::serde_json::to_string(&val).unwrap_or_else(|_| String::new())
-                            ),
-                            |mut acc, message| acc + message + ", ",
-                        );
-                        err_msg.truncate(err_msg.len() - 2);
-                        Err(D::Error::custom(err_msg))
+                impl<'de> serde::Deserialize<'de> for #contract_name {
+                    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                        where D: serde::Deserializer<'de>,
+                    {
+                        #sylvia ::de::deserialize_glue(deserializer)
                     }
                 }
             }
@@ -1136,6 +1390,26 @@ impl<'a> EntryPoints<'a> {
                 quote! {}
             };
 
+            let sudo_not_overridden = override_entry_points
+                .get_entry_point(MsgType::Sudo)
+                .is_none();
+            let sudo_msg_defined = variants
+                .0
+                .iter()
+                .any(|variant| variant.msg_type == MsgType::Sudo);
+
+            let sudo = if sudo_not_overridden && sudo_msg_defined {
+                OverrideEntryPoint::emit_default_entry_point(
+                    &custom_msg,
+                    &custom_query,
+                    name,
+                    error,
+                    MsgType::Sudo,
+                )
+            } else {
+                quote! {}
+            };
+
             let reply_ep = override_entry_points
                 .get_entry_point(MsgType::Reply)
                 .map(|_| quote! {})
@@ -1161,6 +1435,8 @@ impl<'a> EntryPoints<'a> {
 
                     #migrate
 
+                    #sudo
+
                     #reply_ep
                 }
             }
@@ -1,3 +1,4 @@
+use convert_case::{Case, Casing};
 use proc_macro2::{Ident, TokenStream};
 use proc_macro_error::emit_error;
 use quote::quote;
@@ -10,11 +11,12 @@ use syn::{
 use crate::check_generics::CheckGenerics;
 use crate::crate_module;
 use crate::interfaces::Interfaces;
-use crate::message::MsgField;
+use crate::message::{MsgField, MsgVariants};
 use crate::parser::{
     parse_struct_message, Custom, MsgAttr, MsgType, OverrideEntryPoint, OverrideEntryPoints,
 };
 use crate::utils::{extract_return_type, process_fields};
+use crate::variant_descs::AsVariantDescs;
 
 struct MessageSignature<'a> {
     pub name: &'a Ident,
@@ -30,6 +32,7 @@ pub struct MultitestHelpers<'a> {
     contract: &'a Type,
     is_trait: bool,
     is_migrate: bool,
+    is_sudo: bool,
     reply: Option<Ident>,
     source: &'a ItemImpl,
     generics: &'a [&'a GenericParam],
@@ -70,6 +73,7 @@ impl<'a> MultitestHelpers<'a> {
         interfaces: &'a Interfaces,
     ) -> Self {
         let mut is_migrate = false;
+        let mut is_sudo = false;
         let mut reply = None;
         let sylvia = crate_module();
 
@@ -90,6 +94,8 @@ impl<'a> MultitestHelpers<'a> {
 
                     if msg_ty == MsgType::Migrate {
                         is_migrate = true;
+                    } else if msg_ty == MsgType::Sudo {
+                        is_sudo = true;
                     } else if msg_ty == MsgType::Reply {
                         reply = Some(method.sig.ident.clone());
                         return None;
@@ -199,6 +205,7 @@ impl<'a> MultitestHelpers<'a> {
             contract,
             is_trait,
             is_migrate,
+            is_sudo,
             reply,
             source,
             generics,
@@ -269,6 +276,15 @@ impl<'a> MultitestHelpers<'a> {
                             #sylvia ::multitest::MigrateProxy::new(&self.contract_addr, msg, &self.app)
                     }
                 }
+            } else if msg_ty == &MsgType::Sudo {
+                    quote! {
+                        #[track_caller]
+                        pub fn #name (&self, #(#params,)* ) -> #sylvia ::multitest::SudoProxy::<#error_type, SudoMsg, #mt_app> {
+                            let msg = SudoMsg:: #name ( #(#arguments),* );
+
+                            #sylvia ::multitest::SudoProxy::new(&self.contract_addr, msg, &self.app)
+                    }
+                }
             } else if msg_ty == &MsgType::Query {
                     quote! {
                         pub fn #name (&self, #(#params,)* ) -> Result<#return_type, #error_type> {
@@ -286,6 +302,54 @@ impl<'a> MultitestHelpers<'a> {
             }
         });
 
+        let has_reply = self.reply.is_some()
+            || self
+                .override_entry_points
+                .get_entry_point(MsgType::Reply)
+                .is_some();
+
+        #[cfg(not(tarpaulin_include))]
+        let reply = has_reply.then(|| {
+            quote! {
+                /// Calls the reply entry point directly with the crafted `reply`
+                #[track_caller]
+                pub fn reply(&self, reply: #sylvia ::cw_std::Reply) -> Result<#sylvia ::cw_multi_test::AppResponse, #sylvia ::multitest::CallError<#error_type>> {
+                    self.reply_with_response(reply).map(|response| response.response)
+                }
+
+                /// Calls the reply entry point like `reply`, returning the response with the
+                /// details of the call
+                #[track_caller]
+                pub fn reply_with_response(&self, reply: #sylvia ::cw_std::Reply) -> Result<#sylvia ::multitest::CallResponse, #sylvia ::multitest::CallError<#error_type>> {
+                    let response = self.app
+                        .proxy_call(|| self.app.reply(&self.contract_addr, reply.clone()))
+                        .map_err(#sylvia ::multitest::CallError::new)?;
+
+                    self.app
+                        .check_invariants_after(|| {
+                            format!("reply on {} with {:?}\nResponse: {:?}", self.contract_addr, reply, response.response)
+                        })
+                        .map_err(#sylvia ::multitest::CallError::Invariant)?;
+                    Ok(response)
+                }
+            }
+        });
+
+        // Overridden `sudo` entry point takes a single message of its own type
+        #[cfg(not(tarpaulin_include))]
+        let sudo = self
+            .override_entry_points
+            .get_entry_point(MsgType::Sudo)
+            .map(|entry_point| {
+                let msg_name = entry_point.msg_name();
+                quote! {
+                    #[track_caller]
+                    pub fn sudo(&self, msg: #msg_name) -> #sylvia ::multitest::SudoProxy::<#error_type, #msg_name, #mt_app> {
+                        #sylvia ::multitest::SudoProxy::new(&self.contract_addr, msg, &self.app)
+                    }
+                }
+            });
+
         let contract_block = self.generate_contract_helpers();
 
         let proxy_accessors = interfaces.emit_proxy_accessors(&mt_app);
@@ -330,8 +394,25 @@ impl<'a> MultitestHelpers<'a> {
                             #proxy_name{ contract_addr, app }
                         }
 
+                        /// Registers invariant of this contract checked after every call done through the proxies
+                        pub fn invariant(
+                            &self,
+                            name: impl Into<String>,
+                            invariant: impl for<'b> Fn(&#proxy_name<'b, #mt_app >) -> #sylvia ::anyhow::Result<()> + 'static,
+                        ) -> &Self {
+                            let contract_addr = self.contract_addr.clone();
+                            self.app.add_invariant(name, move |app| {
+                                invariant(&#proxy_name::new(contract_addr.clone(), app))
+                            });
+                            self
+                        }
+
                         #(#messages)*
 
+                        #reply
+
+                        #sudo
+
                         #(#proxy_accessors)*
                     }
 
@@ -366,6 +447,12 @@ impl<'a> MultitestHelpers<'a> {
                         }
                     }
 
+                    impl<'app, MtApp> From<#proxy_name <'app, MtApp>> for #sylvia ::cw_std::Addr {
+                        fn from(proxy: #proxy_name <'app, MtApp>) -> #sylvia ::cw_std::Addr {
+                            proxy.contract_addr
+                        }
+                    }
+
                     #contract_block
                 }
             }
@@ -549,6 +636,11 @@ impl<'a> MultitestHelpers<'a> {
         let fields = instantiate_fields.iter().map(MsgField::emit);
 
         let impl_contract = self.generate_impl_contract();
+        let has_migrate = self.is_migrate
+            || self
+                .override_entry_points
+                .get_entry_point(MsgType::Migrate)
+                .is_some();
 
         let custom_msg = self.custom.msg_or_default();
         let custom_query = self.custom.query_or_default();
@@ -590,9 +682,13 @@ impl<'a> MultitestHelpers<'a> {
                         GovT: #sylvia ::cw_multi_test::Gov,
                 {
                     pub fn store_code(app: &'app #sylvia ::multitest::App< #mt_app >) -> Self {
-                        let code_id = app
-                            .app_mut()
-                            .store_code(Box::new(#contract_name ::new()));
+                        let code_id = app.store_tracked_code(
+                            stringify!(#contract_name),
+                            &ContractExecMsg::messages(),
+                            &ContractQueryMsg::messages(),
+                            #has_migrate,
+                            Box::new(#contract_name ::new()),
+                        );
                         Self { code_id, app }
                     }
 
@@ -600,6 +696,15 @@ impl<'a> MultitestHelpers<'a> {
                         self.code_id
                     }
 
+                    /// Address of the contract instantiated by the `creator` with the `salt`
+                    pub fn predict_address(
+                        &self,
+                        creator: &str,
+                        salt: impl Into<#sylvia ::cw_std::Binary>,
+                    ) -> #sylvia ::cw_std::StdResult<#sylvia ::cw_std::Addr> {
+                        self.app.predict_address(self.code_id, creator, &salt.into())
+                    }
+
                     pub fn instantiate(
                         &self,#(#fields,)*
                     ) -> InstantiateProxy<'_, 'app, #mt_app > {
@@ -609,6 +714,7 @@ impl<'a> MultitestHelpers<'a> {
                             funds: &[],
                             label: "Contract",
                             admin: None,
+                            salt: None,
                             msg,
                         }
                     }
@@ -619,12 +725,20 @@ impl<'a> MultitestHelpers<'a> {
                     funds: &'a [#sylvia ::cw_std::Coin],
                     label: &'a str,
                     admin: Option<String>,
+                    salt: Option<#sylvia ::cw_std::Binary>,
                     msg: InstantiateMsg,
                 }
 
-                impl<'a, 'app, MtApp> InstantiateProxy<'a, 'app, MtApp>
+                impl<'a, 'app, BankT, ApiT, StorageT, CustomT, StakingT, DistrT, IbcT, GovT> InstantiateProxy<'a, 'app, #mt_app>
                     where
-                        MtApp: Executor< #custom_msg >,
+                        BankT: #sylvia ::cw_multi_test::Bank,
+                        ApiT: #sylvia ::cw_std::Api,
+                        StorageT: #sylvia ::cw_std::Storage,
+                        CustomT: #sylvia ::cw_multi_test::Module<ExecT = #custom_msg, QueryT = #custom_query >,
+                        StakingT: #sylvia ::cw_multi_test::Staking,
+                        DistrT: #sylvia ::cw_multi_test::Distribution,
+                        IbcT: #sylvia ::cw_multi_test::Ibc,
+                        GovT: #sylvia ::cw_multi_test::Gov,
                 {
                     pub fn with_funds(self, funds: &'a [#sylvia ::cw_std::Coin]) -> Self {
                         Self { funds, ..self }
@@ -639,23 +753,57 @@ impl<'a> MultitestHelpers<'a> {
                         Self { admin, ..self }
                     }
 
+                    /// Instantiates the contract with the instantiate2 semantics, giving it the
+                    /// address predicted by `CodeId::predict_address`. This is the only way to
+                    /// get such address, as multitest does not support `WasmMsg::Instantiate2`.
+                    pub fn with_salt(self, salt: impl Into<#sylvia ::cw_std::Binary>) -> Self {
+                        let salt = Some(salt.into());
+                        Self { salt, ..self }
+                    }
+
+                    #[track_caller]
+                    pub fn call(self, sender: &str) -> Result<#proxy_name<'app, #mt_app>, #sylvia ::multitest::CallError< #error_type >> {
+                        self.call_with_response(sender).map(|response| response.response)
+                    }
+
+                    /// Instantiates the contract like `call`, returning the proxy with the details
+                    /// of the instantiation
                     #[track_caller]
-                    pub fn call(self, sender: &str) -> Result<#proxy_name<'app, MtApp>, #error_type> {
-                        (*self.code_id.app)
-                            .app_mut()
-                            .instantiate_contract(
+                    pub fn call_with_response(self, sender: &str) -> Result<#sylvia ::multitest::CallResponse<#proxy_name<'app, #mt_app>>, #sylvia ::multitest::CallError< #error_type >> {
+                        let app = self.code_id.app;
+                        let instantiate = || {
+                            (*app).app_mut().instantiate_contract(
                                 self.code_id.code_id,
                                 #sylvia ::cw_std::Addr::unchecked(sender),
                                 &self.msg,
                                 self.funds,
                                 self.label,
-                                self.admin,
+                                self.admin.clone(),
                             )
-                            .map_err(|err| err.downcast().unwrap())
-                            .map(|addr| #proxy_name {
-                                contract_addr: addr,
-                                app: self.code_id.app,
+                        };
+                        let response = app
+                            .proxy_call(|| match &self.salt {
+                                Some(salt) => app.instantiate_salted(self.code_id.code_id, sender, salt, instantiate),
+                                None => instantiate(),
+                            })
+                            .map_err(#sylvia ::multitest::CallError::new)?;
+
+                        self.code_id.app
+                            .check_invariants_after(|| {
+                                format!(
+                                    "instantiation of {} from code {} by {} with funds {:?}\nMessage: {}",
+                                    response.response,
+                                    self.code_id.code_id,
+                                    sender,
+                                    self.funds,
+                                    #sylvia ::multitest::report_msg(&self.msg),
+                                )
                             })
+                            .map_err(#sylvia ::multitest::CallError::Invariant)?;
+                        Ok(response.map(|contract_addr| #proxy_name {
+                            contract_addr,
+                            app: self.code_id.app,
+                        }))
                     }
                 }
             }
@@ -688,14 +836,15 @@ impl<'a> MultitestHelpers<'a> {
             .map(OverrideEntryPoint::emit_multitest_dispatch)
             .unwrap_or_else(|| OverrideEntryPoint::emit_multitest_default_dispatch(MsgType::Query));
 
-        let sudo_body = override_entry_points
-            .get_entry_point(MsgType::Sudo)
-            .map(OverrideEntryPoint::emit_multitest_dispatch)
-            .unwrap_or_else(|| {
-                quote! {
-                    #sylvia ::anyhow::bail!("sudo not implemented for contract")
-                }
-            });
+        let sudo_body = match override_entry_points.get_entry_point(MsgType::Sudo) {
+            Some(entry_point) => entry_point.emit_multitest_dispatch(),
+            None if self.is_sudo => {
+                OverrideEntryPoint::emit_multitest_default_dispatch(MsgType::Sudo)
+            }
+            None => quote! {
+                #sylvia ::anyhow::bail!("sudo not implemented for contract")
+            },
+        };
 
         let migrate_body = match override_entry_points.get_entry_point(MsgType::Migrate) {
             Some(entry_point) => entry_point.emit_multitest_dispatch(),
@@ -794,12 +943,19 @@ impl<'a> MultitestHelpers<'a> {
 
 pub struct TraitMultitestHelpers<'a> {
     trait_name: &'a Ident,
+    variants: MsgVariants<'a>,
+    generics: Vec<&'a GenericParam>,
 }
 
 impl<'a> TraitMultitestHelpers<'a> {
     pub fn new(source: &'a ItemTrait) -> Self {
+        let generics: Vec<_> = source.generics.params.iter().collect();
+        let variants = MsgVariants::new(source.as_variants(), &generics);
+
         Self {
             trait_name: &source.ident,
+            variants,
+            generics,
         }
     }
 
@@ -807,10 +963,13 @@ impl<'a> TraitMultitestHelpers<'a> {
         let trait_name = self.trait_name;
         let sylvia = crate_module();
         let proxy_name = Ident::new(&format!("{}Proxy", trait_name), trait_name.span());
+        let (mock, mock_handlers) = self.emit_mock();
 
         #[cfg(not(tarpaulin_include))]
         {
             quote! {
+                #mock_handlers
+
                 pub mod trait_utils {
                     pub struct #proxy_name <'app, MtApp> {
                         pub contract_addr: #sylvia ::cw_std::Addr,
@@ -827,8 +986,138 @@ impl<'a> TraitMultitestHelpers<'a> {
                             self.contract_addr
                         }
                     }
+
+                    #mock
                 }
             }
         }
     }
+
+    /// Emits `Mock` wrapper with the handlers taking messages fields as arguments.
+    ///
+    /// The wrapper is emitted into the `trait_utils` module, while its handlers are implemented
+    /// next to the interface, where the types of the messages fields are in scope.
+    ///
+    /// Mocks are not generated for generic interfaces.
+    fn emit_mock(&self) -> (TokenStream, TokenStream) {
+        let Self {
+            trait_name,
+            variants,
+            generics,
+        } = self;
+
+        if !generics.is_empty() {
+            return (quote! {}, quote! {});
+        }
+
+        let sylvia = crate_module();
+        let mock_name = Ident::new(&format!("{}Mock", trait_name), trait_name.span());
+        let handlers = variants.variants().iter().map(|variant| {
+            let name = variant.name();
+            let msg_name = name.to_string().to_case(Case::Snake);
+            let on_name = Ident::new(&format!("on_{}", msg_name), name.span());
+            let fields: Vec<_> = variant.fields().iter().map(MsgField::name).collect();
+            let types = variant.fields().iter().map(MsgField::ty);
+
+            #[cfg(not(tarpaulin_include))]
+            match variant.msg_type() {
+                MsgType::Exec => quote! {
+                    pub fn #on_name(
+                        self,
+                        handler: impl Fn(#sylvia ::types::ExecCtx<QueryC>, #(#types,)*) -> #sylvia ::cw_std::StdResult<#sylvia ::cw_std::Response<ExecC>> + 'static,
+                    ) -> Self {
+                        Self(self.0.on_exec(#msg_name, move |ctx, msg| match msg {
+                            ExecMsg:: #name { #(#fields,)* } => handler(ctx, #(#fields,)*),
+                            #[allow(unreachable_patterns)]
+                            _ => unreachable!(),
+                        }))
+                    }
+                },
+                MsgType::Query => {
+                    let return_type = variant.return_type();
+                    let returns_name = Ident::new(&format!("{}_returns", msg_name), name.span());
+
+                    quote! {
+                        pub fn #on_name(
+                            self,
+                            handler: impl Fn(#sylvia ::types::QueryCtx<QueryC>, #(#types,)*) -> #sylvia ::cw_std::StdResult<#return_type> + 'static,
+                        ) -> Self {
+                            Self(self.0.on_query(#msg_name, move |ctx, msg| match msg {
+                                QueryMsg:: #name { #(#fields,)* } => #sylvia ::cw_std::to_binary(&handler(ctx, #(#fields,)*)?),
+                                #[allow(unreachable_patterns)]
+                                _ => unreachable!(),
+                            }))
+                        }
+
+                        pub fn #returns_name(self, response: #return_type) -> Self {
+                            Self(self.0.query_returns(#msg_name, &response))
+                        }
+                    }
+                }
+                _ => quote! {},
+            }
+        });
+
+        #[cfg(not(tarpaulin_include))]
+        let mt_app = quote! {
+            #sylvia ::cw_multi_test::App<
+                BankT,
+                ApiT,
+                StorageT,
+                CustomT,
+                #sylvia ::cw_multi_test::WasmKeeper<ExecC, QueryC>,
+                StakingT,
+                DistrT,
+                IbcT,
+                GovT,
+            >
+        };
+
+        #[cfg(not(tarpaulin_include))]
+        let mock_handlers = quote! {
+            impl<ExecC, QueryC: #sylvia ::cw_std::CustomQuery> trait_utils:: #mock_name <ExecC, QueryC> {
+                #(#handlers)*
+            }
+        };
+
+        #[cfg(not(tarpaulin_include))]
+        let mock = quote! {
+            pub struct #mock_name <ExecC, QueryC: #sylvia ::cw_std::CustomQuery>(
+                pub #sylvia ::multitest::Mock<super::ExecMsg, super::QueryMsg, ExecC, QueryC>,
+            );
+
+            impl<ExecC, QueryC: #sylvia ::cw_std::CustomQuery> Default for #mock_name <ExecC, QueryC> {
+                fn default() -> Self {
+                    Self(#sylvia ::multitest::Mock::new())
+                }
+            }
+
+            impl<ExecC, QueryC: #sylvia ::cw_std::CustomQuery> #mock_name <ExecC, QueryC> {
+                pub fn new() -> Self {
+                    Self::default()
+                }
+
+                pub fn store_code<'app, BankT, ApiT, StorageT, CustomT, StakingT, DistrT, IbcT, GovT>(
+                    self,
+                    app: &'app #sylvia ::multitest::App< #mt_app >,
+                ) -> #sylvia ::multitest::MockCodeId<'app, super::ExecMsg, #mt_app >
+                where
+                    ExecC: std::fmt::Debug + Clone + PartialEq + #sylvia ::schemars::JsonSchema + #sylvia ::serde::de::DeserializeOwned + 'static,
+                    QueryC: #sylvia ::serde::de::DeserializeOwned + 'static,
+                    BankT: #sylvia ::cw_multi_test::Bank,
+                    ApiT: #sylvia ::cw_std::Api,
+                    StorageT: #sylvia ::cw_std::Storage,
+                    CustomT: #sylvia ::cw_multi_test::Module<ExecT = ExecC, QueryT = QueryC>,
+                    StakingT: #sylvia ::cw_multi_test::Staking,
+                    DistrT: #sylvia ::cw_multi_test::Distribution,
+                    IbcT: #sylvia ::cw_multi_test::Ibc,
+                    GovT: #sylvia ::cw_multi_test::Gov,
+                {
+                    self.0.store_code(app)
+                }
+            }
+        };
+
+        (mock, mock_handlers)
+    }
 }
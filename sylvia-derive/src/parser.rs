@@ -14,20 +14,33 @@ use crate::crate_module;
 pub struct ContractArgs {
     /// Module name wrapping generated messages, by default no additional module is created
     pub module: Option<Path>,
+    /// Generate only messages and queriers, skipping dispatching and the impl block itself
+    pub messages_only: bool,
+    /// Skip `Debug` and `PartialEq` derives of generated messages on wasm
+    pub slim: bool,
 }
 
 impl Parse for ContractArgs {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut module = None;
+        let mut messages_only = false;
+        let mut slim = false;
 
         while !input.is_empty() {
             let attr: Ident = input.parse()?;
-            let _: Token![=] = input.parse()?;
 
             if attr == "module" {
+                let _: Token![=] = input.parse()?;
                 module = Some(input.parse()?);
+            } else if attr == "messages_only" {
+                messages_only = true;
+            } else if attr == "slim" {
+                slim = true;
             } else {
-                return Err(Error::new(attr.span(), "expected `module`"));
+                return Err(Error::new(
+                    attr.span(),
+                    "expected `module`, `messages_only` or `slim`",
+                ));
             }
 
             if input.peek(Token![,]) {
@@ -39,7 +52,11 @@ impl Parse for ContractArgs {
 
         let _: Nothing = input.parse()?;
 
-        Ok(ContractArgs { module })
+        Ok(ContractArgs {
+            module,
+            messages_only,
+            slim,
+        })
     }
 }
 
@@ -61,6 +78,7 @@ pub enum MsgAttr {
     Instantiate { name: Ident },
     Migrate { name: Ident },
     Reply,
+    Sudo,
 }
 
 impl MsgType {
@@ -149,7 +167,7 @@ impl MsgType {
             MsgType::Instantiate => parse_quote! { InstantiateMsg },
             MsgType::Migrate => parse_quote! { MigrateMsg },
             MsgType::Reply => parse_quote! { ReplyMsg },
-            MsgType::Sudo => todo!(),
+            MsgType::Sudo => parse_quote! { SudoMsg },
         }
     }
 }
@@ -182,6 +200,7 @@ impl MsgAttr {
             Instantiate { .. } => MsgType::Instantiate,
             Migrate { .. } => MsgType::Migrate,
             Reply => MsgType::Reply,
+            Sudo => MsgType::Sudo,
         }
     }
 }
@@ -204,10 +223,12 @@ impl Parse for MsgAttr {
             Ok(Self::Migrate { name })
         } else if ty == "reply" {
             Ok(Self::Reply)
+        } else if ty == "sudo" {
+            Ok(Self::Sudo)
         } else {
             Err(Error::new(
                 ty.span(),
-                "Invalid message type, expected one of: `exec`, `query`, `instantiate`, `migrate`",
+                "Invalid message type, expected one of: `exec`, `query`, `instantiate`, `migrate`, `reply`, `sudo`",
             ))
         }
     }
@@ -514,6 +535,10 @@ pub struct OverrideEntryPoint {
 }
 
 impl OverrideEntryPoint {
+    pub fn msg_name(&self) -> &Type {
+        &self.msg_name
+    }
+
     pub fn emit_multitest_dispatch(&self) -> TokenStream {
         let Self {
             entry_point,
@@ -0,0 +1,333 @@
+//! Building contract messages from the command line.
+//!
+//! With the `cli` feature every generated message implements [`CliMsg`]. Enum messages add a
+//! subcommand per variant, struct messages add their fields as arguments. Fields are passed as
+//! kebab-case flags taking a JSON value; plain strings may be passed without quotes.
+//!
+//! [`Cli`] composes the messages of a contract into a single command printing the built message
+//! as JSON, or for execute messages, as a base64 encoded `MsgExecuteContract` body ready to be
+//! signed. Nothing is ever sent to the chain.
+//!
+//! ```ignore
+//! fn main() {
+//!     sylvia::cli::Cli::new("cw20-base")
+//!         .instantiate::<InstantiateMsg>()
+//!         .exec::<ContractExecMsg>()
+//!         .query::<ContractQueryMsg>()
+//!         .run();
+//! }
+//! ```
+//!
+//! ```sh
+//! $ cw20-base exec transfer --recipient addr0001 --amount 100
+//! {"transfer":{"recipient":"addr0001","amount":"100"}}
+//! ```
+
+use std::ffi::OsString;
+
+use clap::error::ErrorKind;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use cosmwasm_std::{Binary, Coin, Uint128};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::types::SylviaQueryMsg;
+
+/// Message which can be built from the command line arguments
+pub trait CliMsg: Sized {
+    /// Adds arguments of the message to the `cmd`
+    fn augment_command(cmd: Command) -> Command;
+
+    /// Builds the message from arguments matched against command returned by `augment_command`
+    fn from_matches(matches: &ArgMatches) -> Result<Self, clap::Error>;
+}
+
+impl CliMsg for SylviaQueryMsg {
+    fn augment_command(cmd: Command) -> Command {
+        cmd.subcommand(Command::new("sylvia_info"))
+    }
+
+    fn from_matches(matches: &ArgMatches) -> Result<Self, clap::Error> {
+        match matches.subcommand_name() {
+            Some("sylvia_info") => Ok(Self::SylviaInfo {}),
+            _ => Err(unknown_message(matches)),
+        }
+    }
+}
+
+/// Creates argument for message field. Fields of `Option` type are not required.
+pub fn field_arg(id: &'static str, flag: &'static str, ty: &'static str, optional: bool) -> Arg {
+    Arg::new(id).long(flag).value_name(ty).required(!optional)
+}
+
+/// Parses value of message field
+///
+/// The value is read as JSON. If it is not a valid JSON value of the field type, it is treated
+/// as a plain string, so `--recipient addr0001` or `--amount 100` work without quoting.
+/// Missing argument is read as `null`.
+pub fn parse_field<T: DeserializeOwned>(matches: &ArgMatches, id: &str) -> Result<T, clap::Error> {
+    let Some(raw) = matches.get_one::<String>(id) else {
+        return serde_json::from_value(Value::Null).map_err(|err| invalid_field(id, err));
+    };
+
+    if let Ok(value) = serde_json::from_str::<Value>(raw) {
+        let composite = value.is_object() || value.is_array();
+        match serde_json::from_value(value) {
+            Ok(field) => return Ok(field),
+            // Error of composite value is more helpful than the one of the string fallback
+            Err(err) if composite => return Err(invalid_field(id, err)),
+            Err(_) => (),
+        }
+    }
+
+    serde_json::from_value(Value::String(raw.clone())).map_err(|err| invalid_field(id, err))
+}
+
+/// Error returned when the subcommand doesn't match any message
+pub fn unknown_message(matches: &ArgMatches) -> clap::Error {
+    let msg = match matches.subcommand_name() {
+        Some(name) => format!("Unsupported message: {name}\n"),
+        None => "Message not provided\n".to_owned(),
+    };
+    clap::Error::raw(ErrorKind::InvalidSubcommand, msg)
+}
+
+fn invalid_field(id: &str, err: serde_json::Error) -> clap::Error {
+    clap::Error::raw(
+        ErrorKind::ValueValidation,
+        format!("Invalid value for `--{}`: {}\n", id.replace('_', "-"), err),
+    )
+}
+
+type MsgBuilder = fn(&ArgMatches) -> Result<Vec<u8>, clap::Error>;
+
+/// Builds message, serializes it and deserializes it back to make sure the contract accepts it
+fn build_msg<M: CliMsg + Serialize + DeserializeOwned>(
+    matches: &ArgMatches,
+) -> Result<Vec<u8>, clap::Error> {
+    let msg = M::from_matches(matches)?;
+    let msg = serde_json::to_vec(&msg)
+        .map_err(|err| clap::Error::raw(ErrorKind::ValueValidation, format!("{err}\n")))?;
+    serde_json::from_slice::<M>(&msg)
+        .map_err(|err| clap::Error::raw(ErrorKind::ValueValidation, format!("{err}\n")))?;
+    Ok(msg)
+}
+
+/// Command line tool building messages of a single contract
+pub struct Cli {
+    command: Command,
+    builders: Vec<(String, MsgBuilder)>,
+}
+
+impl Cli {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            command: Command::new(name)
+                .about("Builds messages of the contract")
+                .subcommand_required(true),
+            builders: vec![],
+        }
+    }
+
+    /// Adds `instantiate` command building `M`
+    pub fn instantiate<M: CliMsg + Serialize + DeserializeOwned>(self) -> Self {
+        let cmd = Command::new("instantiate").about("Builds instantiate message");
+        self.with_msg::<M>(M::augment_command(cmd))
+    }
+
+    /// Adds `migrate` command building `M`
+    pub fn migrate<M: CliMsg + Serialize + DeserializeOwned>(self) -> Self {
+        let cmd = Command::new("migrate").about("Builds migrate message");
+        self.with_msg::<M>(M::augment_command(cmd))
+    }
+
+    /// Adds `query` command with subcommands of `M`
+    pub fn query<M: CliMsg + Serialize + DeserializeOwned>(self) -> Self {
+        let cmd = Command::new("query")
+            .about("Builds query message")
+            .subcommand_required(true);
+        self.with_msg::<M>(M::augment_command(cmd))
+    }
+
+    /// Adds `exec` command with subcommands of `M`
+    ///
+    /// With `--encode` the message is wrapped in `MsgExecuteContract` and printed as base64
+    /// encoded protobuf.
+    pub fn exec<M: CliMsg + Serialize + DeserializeOwned>(self) -> Self {
+        let cmd = Command::new("exec")
+            .about("Builds execute message")
+            .subcommand_required(true)
+            .arg(
+                Arg::new("encode")
+                    .long("encode")
+                    .action(ArgAction::SetTrue)
+                    .requires_all(["sender", "contract"])
+                    .help("Prints base64 encoded `MsgExecuteContract` instead of JSON message"),
+            )
+            .arg(
+                Arg::new("sender")
+                    .long("sender")
+                    .value_name("ADDR")
+                    .requires("encode")
+                    .help("Address executing the message"),
+            )
+            .arg(
+                Arg::new("contract")
+                    .long("contract")
+                    .value_name("ADDR")
+                    .requires("encode")
+                    .help("Address of the executed contract"),
+            )
+            .arg(
+                Arg::new("funds")
+                    .long("funds")
+                    .value_name("COINS")
+                    .value_parser(parse_coins)
+                    .requires("encode")
+                    .help("Funds sent with the message, eg. `100uatom,5ujuno`"),
+            );
+        self.with_msg::<M>(M::augment_command(cmd))
+    }
+
+    fn with_msg<M: CliMsg + Serialize + DeserializeOwned>(mut self, cmd: Command) -> Self {
+        self.builders
+            .push((cmd.get_name().to_owned(), build_msg::<M>));
+        self.command = self.command.subcommand(cmd);
+        self
+    }
+
+    /// Parses `args` and returns the built message
+    pub fn try_run_from<I, T>(self, args: I) -> Result<String, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = self.command.try_get_matches_from(args)?;
+        let Some((name, cmd_matches)) = matches.subcommand() else {
+            return Err(unknown_message(&matches));
+        };
+        let Some((_, builder)) = self.builders.iter().find(|(cmd, _)| *cmd == name) else {
+            return Err(unknown_message(&matches));
+        };
+        let msg = builder(cmd_matches)?;
+
+        if name == "exec" && cmd_matches.get_flag("encode") {
+            let funds = cmd_matches
+                .get_one::<Vec<Coin>>("funds")
+                .cloned()
+                .unwrap_or_default();
+            // Both are required by `--encode`
+            let sender = cmd_matches.get_one::<String>("sender").unwrap();
+            let contract = cmd_matches.get_one::<String>("contract").unwrap();
+            let encoded = encode_msg_execute_contract(sender, contract, &msg, &funds);
+            return Ok(Binary(encoded).to_base64());
+        }
+
+        String::from_utf8(msg)
+            .map_err(|err| clap::Error::raw(ErrorKind::InvalidUtf8, format!("{err}\n")))
+    }
+
+    /// Parses the process arguments and prints the built message, exiting on error
+    pub fn run(self) {
+        match self.try_run_from(std::env::args_os()) {
+            Ok(output) => println!("{output}"),
+            Err(err) => err.exit(),
+        }
+    }
+}
+
+/// Parses comma separated list of coins, eg. `100uatom,5ujuno`
+fn parse_coins(coins: &str) -> Result<Vec<Coin>, String> {
+    coins
+        .split(',')
+        .map(|coin| {
+            let coin = coin.trim();
+            let split = coin
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(|| format!("Missing denom in `{coin}`"))?;
+            let (amount, denom) = coin.split_at(split);
+            let amount: u128 = amount
+                .parse()
+                .map_err(|_| format!("Invalid amount in `{coin}`"))?;
+            Ok(Coin::new(amount, denom))
+        })
+        .collect()
+}
+
+/// Encodes `cosmwasm.wasm.v1.MsgExecuteContract` in protobuf wire format
+fn encode_msg_execute_contract(
+    sender: &str,
+    contract: &str,
+    msg: &[u8],
+    funds: &[Coin],
+) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_bytes(&mut buf, 1, sender.as_bytes());
+    encode_bytes(&mut buf, 2, contract.as_bytes());
+    encode_bytes(&mut buf, 3, msg);
+    for coin in funds {
+        encode_bytes(&mut buf, 5, &encode_coin(&coin.denom, coin.amount));
+    }
+    buf
+}
+
+/// Encodes `cosmos.base.v1beta1.Coin` in protobuf wire format
+fn encode_coin(denom: &str, amount: Uint128) -> Vec<u8> {
+    let mut buf = vec![];
+    encode_bytes(&mut buf, 1, denom.as_bytes());
+    encode_bytes(&mut buf, 2, amount.to_string().as_bytes());
+    buf
+}
+
+/// Encodes length delimited field
+fn encode_bytes(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    if bytes.is_empty() {
+        // Proto3 skips default values
+        return;
+    }
+    encode_varint(buf, field << 3 | 2);
+    encode_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn encode_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coin;
+
+    use super::*;
+
+    #[test]
+    fn coins() {
+        assert_eq!(
+            parse_coins("100uatom,5ujuno").unwrap(),
+            vec![coin(100, "uatom"), coin(5, "ujuno")]
+        );
+        assert!(parse_coins("uatom").is_err());
+        assert!(parse_coins("100").is_err());
+    }
+
+    #[test]
+    fn msg_execute_contract() {
+        let encoded = encode_msg_execute_contract("a", "b", b"{}", &[coin(5, "x")]);
+        assert_eq!(
+            encoded,
+            [
+                0x0a, 1, b'a', 0x12, 1, b'b', 0x1a, 2, b'{', b'}', 0x2a, 6, 0x0a, 1, b'x', 0x12, 1,
+                b'5'
+            ]
+        );
+
+        let mut buf = vec![];
+        encode_varint(&mut buf, 300);
+        assert_eq!(buf, [0xac, 0x02]);
+    }
+}
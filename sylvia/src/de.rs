@@ -0,0 +1,524 @@
+//! Helpers used by the `Deserialize` implementation of the generated `ContractExecMsg` and
+//! `ContractQueryMsg`.
+//!
+//! Glue messages are untagged enums over the contract and interfaces messages, all of them
+//! being externally tagged. Instead of buffering the whole message to find out which variant
+//! it belongs to, [`deserialize_glue`] reads only the top-level key with [`MsgName`], and the
+//! [`GlueMessage`] picks the variant basing on it and deserializes the payload straight from
+//! the input.
+//!
+//! Failures are reported as [`MessageError`] rendered into the deserializer error. The input is
+//! not buffered, so it cannot be parsed again on failure - the entry points track the path to the
+//! invalid field while deserializing the payload instead. The path is built only on failure, so
+//! valid messages pay just for passing it down. [`from_slice`] returns the structured error.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{
+    DeserializeSeed, Deserializer, EnumAccess, Error, IgnoredAny, IntoDeserializer, MapAccess,
+    VariantAccess, Visitor,
+};
+use serde::{forward_to_deserialize_any, Deserialize};
+use serde_path_to_error::{Segment, Track};
+
+/// Error of the glue message deserialization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageError {
+    /// Received message is not handled by the contract
+    UnsupportedMessage {
+        received: String,
+        /// Closest of the supported messages, if any is similar enough to be a typo
+        suggestion: Option<String>,
+        supported: Vec<String>,
+    },
+    /// Message payload is invalid. `path` points to the offending field, starting with the
+    /// message name, e.g. `transfer.amount`, and is empty if the message is not a JSON object.
+    InvalidMessage { path: String, error: String },
+    /// Message object has other number of keys than one
+    MessagesCount { received: usize },
+}
+
+impl MessageError {
+    /// Creates [`MessageError::UnsupportedMessage`] suggesting the closest of `supported` messages
+    pub fn unsupported<'a>(received: &str, supported: impl IntoIterator<Item = &'a str>) -> Self {
+        let supported: Vec<_> = supported.into_iter().map(str::to_owned).collect();
+        let max_distance = (received.len() / 3).max(1);
+        let suggestion = supported
+            .iter()
+            .map(|msg| (levenshtein(received, msg), msg))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, msg)| msg.clone());
+
+        Self::UnsupportedMessage {
+            received: received.to_owned(),
+            suggestion,
+            supported,
+        }
+    }
+}
+
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedMessage {
+                received,
+                suggestion,
+                supported,
+            } => {
+                write!(f, "Unsupported message received: {}", received)?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, ". Did you mean `{}`?", suggestion)?;
+                } else {
+                    write!(f, ".")?;
+                }
+                write!(
+                    f,
+                    " Messages supported by this contract: {}",
+                    supported.join(", ")
+                )
+            }
+            Self::InvalidMessage { path, error } if path.is_empty() => write!(f, "{}", error),
+            Self::InvalidMessage { path, error } => write!(f, "{}: {}", path, error),
+            Self::MessagesCount { received } => {
+                write!(f, "Expected exactly one message. Received {}", received)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MessageError {}
+
+/// Edit distance between two message names
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<_> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev + (ca != *cb) as usize;
+            prev = row[j + 1];
+            row[j + 1] = substitution.min(prev + 1).min(row[j] + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Name of the received message, borrowed from the input whenever possible
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgName<'de>(Cow<'de, str>);
+
+impl<'de> MsgName<'de> {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for MsgName<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct NameVisitor;
+
+        impl<'de> Visitor<'de> for NameVisitor {
+            type Value = MsgName<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("message name")
+            }
+
+            fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+                Ok(MsgName(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(MsgName(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(MsgName(Cow::Owned(v)))
+            }
+        }
+
+        deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+/// Glue message over the contract and interfaces messages, implemented by the generated
+/// `ContractExecMsg` and `ContractQueryMsg`
+pub trait GlueMessage: Sized {
+    /// Names of all the messages, including the interfaces ones
+    fn messages() -> Vec<&'static str>;
+
+    /// Checks if the `name` message is handled by any of the glued messages
+    fn is_message(name: &str) -> bool;
+
+    /// Deserializes the payload of the `name` message into the glued message handling it
+    fn deserialize_payload<'de, D>(name: &str, payload: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+}
+
+/// `Deserialize` implementation of the glue messages.
+///
+/// Errors of the message payload are reported as [`MessageError::InvalidMessage`] pointing to
+/// the invalid field.
+pub fn deserialize_glue<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: GlueMessage,
+    D: Deserializer<'de>,
+{
+    deserializer
+        .deserialize_map(GlueVisitor::<T>::new(true))?
+        .map_err(D::Error::custom)
+}
+
+/// Deserializes the glue message from JSON the same way as the entry points, returning the
+/// structured error.
+///
+/// If the payload is invalid, the message is parsed again to find the path to the invalid field
+/// apart from the error itself.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn from_slice<T>(msg: &[u8]) -> Result<T, MessageError>
+where
+    T: GlueMessage + serde::de::DeserializeOwned,
+{
+    /// Glue message with the structured errors kept as a value
+    struct Checked<T>(Result<T, MessageError>);
+
+    impl<'de, T: GlueMessage> Deserialize<'de> for Checked<T> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer
+                .deserialize_map(GlueVisitor::<T>::new(false))
+                .map(Checked)
+        }
+    }
+
+    let error = match serde_json_wasm::from_slice::<Checked<T>>(msg) {
+        Ok(Checked(msg)) => return msg,
+        Err(err) => err.to_string(),
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_slice(msg);
+    let path = match serde_path_to_error::deserialize::<_, T>(&mut deserializer) {
+        Err(err) if err.path().iter().next().is_some() => err.path().to_string(),
+        _ => String::new(),
+    };
+    Err(MessageError::InvalidMessage { path, error })
+}
+
+/// Deserializes externally tagged message `T` from the `payload` of already read `name` key
+pub fn deserialize_tagged<'de, T, D>(name: &str, payload: D) -> Result<T, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(TaggedDeserializer { name, payload })
+}
+
+/// Visits the glue message object, reporting the failures of the message itself as the value.
+///
+/// Other messages are skipped on failure, so the whole input is consumed.
+struct GlueVisitor<T> {
+    /// Whether to report the payload errors as [`MessageError::InvalidMessage`] with the path to
+    /// the invalid field
+    report_payload: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> GlueVisitor<T> {
+    fn new(report_payload: bool) -> Self {
+        Self {
+            report_payload,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for GlueVisitor<T>
+where
+    T: GlueMessage,
+{
+    type Value = Result<T, MessageError>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("message object with exactly one key")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let name: MsgName = match map.next_key()? {
+            Some(name) => name,
+            None => return Ok(Err(MessageError::MessagesCount { received: 0 })),
+        };
+
+        let msg = if T::is_message(name.as_str()) {
+            Ok(map.next_value_seed(PayloadSeed::<T> {
+                name: name.as_str(),
+                report: self.report_payload,
+                _phantom: PhantomData,
+            })?)
+        } else {
+            map.next_value::<IgnoredAny>()?;
+            Err(MessageError::unsupported(name.as_str(), T::messages()))
+        };
+
+        let mut received = 1;
+        while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {
+            received += 1;
+        }
+        if msg.is_ok() && received != 1 {
+            return Ok(Err(MessageError::MessagesCount { received }));
+        }
+
+        Ok(msg)
+    }
+}
+
+/// Payload of the `name` message, to be passed to [`MapAccess::next_value_seed`] right after
+/// the key was read
+struct PayloadSeed<'a, T> {
+    name: &'a str,
+    report: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, 'de, T> DeserializeSeed<'de> for PayloadSeed<'a, T>
+where
+    T: GlueMessage,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if !self.report {
+            return T::deserialize_payload(self.name, deserializer);
+        }
+
+        let mut track = Track::new();
+        let payload = serde_path_to_error::Deserializer::new(deserializer, &mut track);
+        let err = match T::deserialize_payload(self.name, payload) {
+            Ok(msg) => return Ok(msg),
+            Err(err) => err,
+        };
+
+        let path = track.path();
+        let path = match path.iter().next() {
+            None => self.name.to_owned(),
+            Some(Segment::Seq { .. }) => format!("{}{}", self.name, path),
+            Some(_) => format!("{}.{}", self.name, path),
+        };
+        Err(D::Error::custom(MessageError::InvalidMessage {
+            path,
+            error: err.to_string(),
+        }))
+    }
+}
+
+/// Deserializer presenting already read `name` along with not yet consumed `payload` as a single
+/// entry map `{ name: payload }`
+struct TaggedDeserializer<'a, D> {
+    name: &'a str,
+    payload: D,
+}
+
+impl<'a, 'de, D> Deserializer<'de> for TaggedDeserializer<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(TaggedMap {
+            name: Some(self.name),
+            payload: Some(self.payload),
+        })
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'a, 'de, D> EnumAccess<'de> for TaggedDeserializer<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+    type Variant = PayloadAccess<D>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(self.name.into_deserializer())?;
+        Ok((variant, PayloadAccess(self.payload)))
+    }
+}
+
+struct TaggedMap<'a, D> {
+    name: Option<&'a str>,
+    payload: Option<D>,
+}
+
+impl<'a, 'de, D> MapAccess<'de> for TaggedMap<'a, D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.name
+            .take()
+            .map(|name| seed.deserialize(name.into_deserializer()))
+            .transpose()
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.payload.take() {
+            Some(payload) => seed.deserialize(payload),
+            None => Err(Error::custom("message payload already consumed")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.name.is_some() as usize)
+    }
+}
+
+/// Payload of the message as a variant of the externally tagged enum
+struct PayloadAccess<D>(D);
+
+impl<'de, D> VariantAccess<'de> for PayloadAccess<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Deserialize::deserialize(self.0)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.0)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_struct("", fields, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein() {
+        assert_eq!(super::levenshtein("transfer", "transfer"), 0);
+        assert_eq!(super::levenshtein("tranfer", "transfer"), 1);
+        assert_eq!(super::levenshtein("", "mint"), 4);
+        assert_eq!(super::levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn suggestion() {
+        let supported = ["burn", "mint", "transfer", "transfer_from"];
+
+        let err = MessageError::unsupported("tranfer", supported);
+        assert!(
+            matches!(&err, MessageError::UnsupportedMessage { suggestion: Some(s), .. } if s == "transfer")
+        );
+
+        let err = MessageError::unsupported("foo", supported);
+        assert!(matches!(
+            &err,
+            MessageError::UnsupportedMessage {
+                suggestion: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn display() {
+        let err = MessageError::unsupported("tranfer", ["mint", "transfer"]);
+        assert_eq!(
+            err.to_string(),
+            "Unsupported message received: tranfer. Did you mean `transfer`? Messages supported by this contract: mint, transfer"
+        );
+
+        let err = MessageError::unsupported("foo", ["mint", "transfer"]);
+        assert_eq!(
+            err.to_string(),
+            "Unsupported message received: foo. Messages supported by this contract: mint, transfer"
+        );
+
+        let err = MessageError::InvalidMessage {
+            path: "transfer.recipients[1].amount".to_owned(),
+            error: "Invalid number".to_owned(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "transfer.recipients[1].amount: Invalid number"
+        );
+
+        let err = MessageError::InvalidMessage {
+            path: String::new(),
+            error: "Invalid type".to_owned(),
+        };
+        assert_eq!(err.to_string(), "Invalid type");
+    }
+}
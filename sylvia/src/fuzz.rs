@@ -0,0 +1,578 @@
+//! Property based testing of the generated messages.
+//!
+//! With the `proptest` feature every generated exec and query message implements [`Generate`],
+//! providing [`proptest`] strategies creating random messages. Fields are generated basing on
+//! their `JsonSchema`, so any type which may be a message field is supported. Strings are biased
+//! towards the addresses known to the [`GenContext`] and towards numbers, so `Addr` and
+//! `Uint128` fields get meaningful values.
+//!
+//! With the `mt` feature enabled as well, [`Fuzzer`] runs random sequences of messages sent
+//! from random senders against a multitest contract and checks user-registered invariants
+//! after every step. Failing sequences are shrunk to a minimal reproducer.
+
+use std::fmt::{Debug, Write};
+use std::sync::Arc;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::strategy::{BoxedStrategy, Just, Union};
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::types::SylviaQueryMsg;
+
+/// Maximal depth of the nested types generated for a field
+const MAX_DEPTH: usize = 6;
+
+/// Message with the strategies generating it
+pub trait Generate: Sized + Debug + 'static {
+    /// Strategies generating each of the messages
+    fn strategies(ctx: &GenContext) -> Vec<BoxedStrategy<Self>>;
+
+    /// Strategy generating any of the messages
+    ///
+    /// Panics if the message has no variants.
+    fn strategy(ctx: &GenContext) -> BoxedStrategy<Self> {
+        let strategies = Self::strategies(ctx);
+        assert!(
+            !strategies.is_empty(),
+            "Cannot generate {}, it has no messages",
+            std::any::type_name::<Self>()
+        );
+        Union::new(strategies).boxed()
+    }
+}
+
+impl Generate for SylviaQueryMsg {
+    fn strategies(_ctx: &GenContext) -> Vec<BoxedStrategy<Self>> {
+        vec![Just(Self::SylviaInfo {}).boxed()]
+    }
+}
+
+/// Data shared by the strategies
+#[derive(Debug, Clone, Default)]
+pub struct GenContext {
+    addresses: Arc<[String]>,
+}
+
+impl GenContext {
+    /// Creates context generating strings from the `addresses` more often
+    pub fn new(addresses: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            addresses: addresses.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn addresses(&self) -> &[String] {
+        &self.addresses
+    }
+
+    /// Strategy generating JSON values deserializable into `T`
+    pub fn field<T: JsonSchema + DeserializeOwned>(&self) -> BoxedStrategy<Value> {
+        let root = schemars::gen::SchemaSettings::draft07()
+            .into_generator()
+            .into_root_schema_for::<T>();
+        let name = T::schema_name();
+
+        self.root_strategy(&root)
+            .prop_filter(format!("Invalid {name}"), |value| {
+                serde_json::from_value::<T>(value.clone()).is_ok()
+            })
+            .boxed()
+    }
+
+    /// Strategy generating message `name` with fields generated by `fields` strategies
+    pub fn message<M: DeserializeOwned + Debug + 'static>(
+        &self,
+        name: &'static str,
+        fields: Vec<(&'static str, BoxedStrategy<Value>)>,
+    ) -> BoxedStrategy<M> {
+        let (names, strategies): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+
+        strategies
+            .prop_filter_map(format!("Invalid {name} message"), move |values| {
+                let fields: Map<_, _> = names
+                    .iter()
+                    .map(|name| name.to_string())
+                    .zip(values)
+                    .collect();
+                let mut msg = Map::new();
+                msg.insert(name.to_owned(), Value::Object(fields));
+                serde_json::from_value(Value::Object(msg)).ok()
+            })
+            .boxed()
+    }
+
+    fn root_strategy(&self, root: &RootSchema) -> BoxedStrategy<Value> {
+        self.object_strategy(&root.schema, root, 0)
+    }
+
+    fn schema_strategy(
+        &self,
+        schema: &Schema,
+        root: &RootSchema,
+        depth: usize,
+    ) -> BoxedStrategy<Value> {
+        match schema {
+            Schema::Bool(_) => Just(Value::Null).boxed(),
+            Schema::Object(schema) => self.object_strategy(schema, root, depth),
+        }
+    }
+
+    fn object_strategy(
+        &self,
+        schema: &SchemaObject,
+        root: &RootSchema,
+        depth: usize,
+    ) -> BoxedStrategy<Value> {
+        if depth > MAX_DEPTH {
+            return Just(Value::Null).boxed();
+        }
+
+        if let Some(value) = &schema.const_value {
+            return Just(value.clone()).boxed();
+        }
+
+        if let Some(values) = schema
+            .enum_values
+            .as_ref()
+            .filter(|values| !values.is_empty())
+        {
+            return select(values.clone()).boxed();
+        }
+
+        if let Some(reference) = &schema.reference {
+            let name = reference.trim_start_matches("#/definitions/");
+            return match root.definitions.get(name) {
+                Some(schema) => self.schema_strategy(schema, root, depth + 1),
+                None => Just(Value::Null).boxed(),
+            };
+        }
+
+        if let Some(subschemas) = &schema.subschemas {
+            let alternatives = match (&subschemas.one_of, &subschemas.any_of, &subschemas.all_of) {
+                (Some(one_of), _, _) => &one_of[..],
+                (_, Some(any_of), _) => &any_of[..],
+                // `allOf` is used by schemars only to attach description to the referenced type
+                (_, _, Some(all_of)) => &all_of[..all_of.len().min(1)],
+                _ => &[],
+            };
+            if !alternatives.is_empty() {
+                let strategies: Vec<_> = alternatives
+                    .iter()
+                    .map(|schema| self.schema_strategy(schema, root, depth + 1))
+                    .collect();
+                return Union::new(strategies).boxed();
+            }
+        }
+
+        let types = match &schema.instance_type {
+            Some(SingleOrVec::Single(ty)) => vec![**ty],
+            Some(SingleOrVec::Vec(types)) => types.clone(),
+            None => return Just(Value::Null).boxed(),
+        };
+
+        let strategies: Vec<_> = types
+            .into_iter()
+            .map(|ty| self.instance_strategy(ty, schema, root, depth))
+            .collect();
+        Union::new(strategies).boxed()
+    }
+
+    fn instance_strategy(
+        &self,
+        ty: InstanceType,
+        schema: &SchemaObject,
+        root: &RootSchema,
+        depth: usize,
+    ) -> BoxedStrategy<Value> {
+        match ty {
+            InstanceType::Null => Just(Value::Null).boxed(),
+            InstanceType::Boolean => any::<bool>().prop_map(Value::Bool).boxed(),
+            InstanceType::Integer => integer_strategy(schema),
+            InstanceType::Number => (-1e6..1e6f64).prop_map(Value::from).boxed(),
+            InstanceType::String => self.string_strategy().prop_map(Value::String).boxed(),
+            InstanceType::Array => {
+                let array = schema.array.as_deref();
+                match array.and_then(|array| array.items.as_ref()) {
+                    Some(SingleOrVec::Single(item)) => {
+                        let min = array.and_then(|array| array.min_items).unwrap_or(0) as usize;
+                        let max = array
+                            .and_then(|array| array.max_items)
+                            .map(|max| max as usize)
+                            .unwrap_or(min + 3)
+                            .max(min);
+                        vec(self.schema_strategy(item, root, depth + 1), min..=max)
+                            .prop_map(Value::Array)
+                            .boxed()
+                    }
+                    Some(SingleOrVec::Vec(items)) => items
+                        .iter()
+                        .map(|item| self.schema_strategy(item, root, depth + 1))
+                        .collect::<Vec<_>>()
+                        .prop_map(Value::Array)
+                        .boxed(),
+                    None => Just(Value::Array(vec![])).boxed(),
+                }
+            }
+            InstanceType::Object => {
+                let Some(object) = schema.object.as_deref() else {
+                    return Just(Value::Object(Map::new())).boxed();
+                };
+
+                let (names, properties): (Vec<_>, Vec<_>) = object
+                    .properties
+                    .iter()
+                    .map(|(name, schema)| {
+                        let strategy = self.schema_strategy(schema, root, depth + 1);
+                        let strategy = if object.required.contains(name) {
+                            strategy.prop_map(Some).boxed()
+                        } else {
+                            proptest::option::of(strategy).boxed()
+                        };
+                        (name.clone(), strategy)
+                    })
+                    .unzip();
+
+                let additional = match &object.additional_properties {
+                    Some(schema) if !matches!(**schema, Schema::Bool(false)) => vec(
+                        (
+                            self.string_strategy(),
+                            self.schema_strategy(schema, root, depth + 1),
+                        ),
+                        0..3,
+                    )
+                    .boxed(),
+                    _ => Just(vec![]).boxed(),
+                };
+
+                (properties, additional)
+                    .prop_map(move |(properties, additional)| {
+                        let mut object: Map<_, _> = additional.into_iter().collect();
+                        object.extend(
+                            names
+                                .iter()
+                                .cloned()
+                                .zip(properties)
+                                .filter_map(|(name, value)| Some((name, value?))),
+                        );
+                        Value::Object(object)
+                    })
+                    .boxed()
+            }
+        }
+    }
+
+    fn string_strategy(&self) -> BoxedStrategy<String> {
+        let numbers = prop_oneof![
+            (0..1000u128),
+            Just(u64::MAX as u128),
+            Just(u128::MAX),
+            any::<u128>(),
+        ]
+        .prop_map(|n| n.to_string());
+        let decimals = (0..1000u64, 0..1000u64).prop_map(|(int, frac)| format!("{int}.{frac}"));
+        let words = vec(
+            select(('a'..='z').chain('0'..='9').collect::<Vec<_>>()),
+            0..8,
+        )
+        .prop_map(String::from_iter);
+        let binaries =
+            vec(any::<u8>(), 0..16).prop_map(|bytes| cosmwasm_std::Binary(bytes).to_base64());
+
+        if self.addresses.is_empty() {
+            prop_oneof![3 => numbers, 1 => decimals, 2 => words, 1 => binaries].boxed()
+        } else {
+            let addresses = select(self.addresses.to_vec());
+            prop_oneof![
+                4 => addresses,
+                3 => numbers,
+                1 => decimals,
+                1 => words,
+                1 => binaries
+            ]
+            .boxed()
+        }
+    }
+}
+
+fn integer_strategy(schema: &SchemaObject) -> BoxedStrategy<Value> {
+    let number = schema.number.as_deref();
+    let (min, max) = match schema.format.as_deref() {
+        Some("uint8") => (0, u8::MAX as i128),
+        Some("uint16") => (0, u16::MAX as i128),
+        Some("uint32") => (0, u32::MAX as i128),
+        Some("uint64") | Some("uint") => (0, u64::MAX as i128),
+        Some("int8") => (i8::MIN as i128, i8::MAX as i128),
+        Some("int16") => (i16::MIN as i128, i16::MAX as i128),
+        Some("int32") => (i32::MIN as i128, i32::MAX as i128),
+        _ => (i64::MIN as i128, i64::MAX as i128),
+    };
+    let min = number
+        .and_then(|number| number.minimum)
+        .map(|minimum| minimum as i128)
+        .unwrap_or(min)
+        .max(i64::MIN as i128);
+    let max = number
+        .and_then(|number| number.maximum)
+        .map(|maximum| maximum as i128)
+        .unwrap_or(max)
+        .max(min);
+
+    let to_value = |n: i128| match u64::try_from(n) {
+        Ok(n) => Value::from(n),
+        Err(_) => Value::from(n as i64),
+    };
+
+    prop_oneof![
+        2 => (min..=max.min(min + 100)).prop_map(to_value),
+        1 => Just(to_value(min)),
+        1 => Just(to_value(max)),
+        1 => (min..=max).prop_map(to_value),
+    ]
+    .boxed()
+}
+
+/// Maps strategies of `T` into strategies of `U`. Used by the glue messages.
+pub fn map_strategies<T: Debug + 'static, U: Debug + 'static>(
+    strategies: Vec<BoxedStrategy<T>>,
+    f: fn(T) -> U,
+) -> Vec<BoxedStrategy<U>> {
+    strategies
+        .into_iter()
+        .map(|strategy| strategy.prop_map(f).boxed())
+        .collect()
+}
+
+#[cfg(feature = "mt")]
+pub use fuzzer::*;
+
+#[cfg(feature = "mt")]
+mod fuzzer {
+    use std::fmt::{Debug, Display};
+    use std::marker::PhantomData;
+    use std::ops::Range;
+
+    use cosmwasm_std::{Addr, Empty};
+    use cw_multi_test::Executor;
+    use proptest::collection::vec;
+    use proptest::sample::select;
+    use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+    use schemars::JsonSchema;
+    use serde::Serialize;
+
+    use super::{GenContext, Generate, Write};
+    use crate::multitest::App;
+
+    type Invariant<MtApp> = Box<dyn Fn(&App<MtApp>, &Addr) -> anyhow::Result<()>>;
+
+    /// Runs random sequences of `Msg` against a multitest contract
+    ///
+    /// Contract is set up from scratch for every sequence. After the setup and after every
+    /// executed message the invariants are checked, including the ones registered on the `App`
+    /// in the setup. Messages are allowed to fail, but a failing
+    /// invariant or a panic fails the run, and the shrunk sequence is reported.
+    ///
+    /// ```ignore
+    /// Fuzzer::<ContractExecMsg>::new()
+    ///     .with_senders(["owner", "alice", "bob"])
+    ///     .invariant("supply", |app, contract| {
+    ///         let contract = Cw20BaseProxy::new(contract.clone(), app);
+    ///         ...
+    ///         Ok(())
+    ///     })
+    ///     .run(|app| {
+    ///         let code_id = CodeId::store_code(app);
+    ///         code_id.instantiate(...).call("owner").unwrap().contract_addr
+    ///     });
+    /// ```
+    #[must_use]
+    pub struct Fuzzer<Msg, MtApp = cw_multi_test::App, ExecC = Empty> {
+        senders: Vec<String>,
+        addresses: Vec<String>,
+        steps: Range<usize>,
+        config: Config,
+        invariants: Vec<(String, Invariant<MtApp>)>,
+        phantom: PhantomData<(Msg, ExecC)>,
+    }
+
+    impl<Msg, MtApp, ExecC> Default for Fuzzer<Msg, MtApp, ExecC> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<Msg, MtApp, ExecC> Fuzzer<Msg, MtApp, ExecC> {
+        pub fn new() -> Self {
+            Self {
+                senders: vec!["owner".to_owned()],
+                addresses: vec![],
+                steps: 1..16,
+                config: Config {
+                    cases: 64,
+                    failure_persistence: None,
+                    ..Config::default()
+                },
+                invariants: vec![],
+                phantom: PhantomData,
+            }
+        }
+
+        /// Addresses sending the messages. Defaults to single `owner` address.
+        pub fn with_senders(self, senders: impl IntoIterator<Item = impl Into<String>>) -> Self {
+            let senders: Vec<_> = senders.into_iter().map(Into::into).collect();
+            assert!(!senders.is_empty(), "Fuzzer requires at least one sender");
+            Self { senders, ..self }
+        }
+
+        /// Additional addresses used in the generated messages along with the senders
+        pub fn with_addresses(
+            self,
+            addresses: impl IntoIterator<Item = impl Into<String>>,
+        ) -> Self {
+            let addresses = addresses.into_iter().map(Into::into).collect();
+            Self { addresses, ..self }
+        }
+
+        /// Range of the number of messages in a sequence
+        pub fn with_steps(self, steps: Range<usize>) -> Self {
+            Self { steps, ..self }
+        }
+
+        /// Number of sequences run
+        pub fn with_cases(mut self, cases: u32) -> Self {
+            self.config.cases = cases;
+            self
+        }
+
+        /// Registers invariant checked after every step
+        pub fn invariant(
+            mut self,
+            name: impl Into<String>,
+            invariant: impl Fn(&App<MtApp>, &Addr) -> anyhow::Result<()> + 'static,
+        ) -> Self {
+            self.invariants.push((name.into(), Box::new(invariant)));
+            self
+        }
+    }
+
+    impl<Msg, MtApp, ExecC> Fuzzer<Msg, MtApp, ExecC>
+    where
+        Msg: Generate + Serialize,
+        MtApp: Executor<ExecC> + Default,
+        ExecC: Debug + Clone + JsonSchema + PartialEq + 'static,
+    {
+        /// Runs the sequences, panicking with the minimal failing sequence
+        #[track_caller]
+        pub fn run(self, setup: impl Fn(&App<MtApp>) -> Addr) {
+            if let Err(err) = self.try_run(setup) {
+                panic!("{err}");
+            }
+        }
+
+        /// Runs the sequences, returning the minimal failing sequence on failure
+        pub fn try_run(self, setup: impl Fn(&App<MtApp>) -> Addr) -> Result<(), FuzzError<Msg>> {
+            let Self {
+                senders,
+                addresses,
+                steps,
+                config,
+                invariants,
+                ..
+            } = self;
+
+            let ctx = GenContext::new(senders.iter().chain(&addresses).cloned());
+            let strategy = vec((select(senders), Msg::strategy(&ctx)), steps);
+
+            let check = |app: &App<MtApp>, contract: &Addr, step: Option<usize>| {
+                let after = || match step {
+                    Some(step) => format!("step {step}"),
+                    None => "setup".to_owned(),
+                };
+                invariants.iter().try_for_each(|(name, invariant)| {
+                    invariant(app, contract).map_err(|err| {
+                        TestCaseError::fail(format!(
+                            "Invariant `{name}` broken after {}: {err}",
+                            after()
+                        ))
+                    })
+                })?;
+                // Invariants registered on the app in the setup are checked too
+                app.check_invariants()
+                    .map_err(|err| TestCaseError::fail(format!("{err}\nAfter {}", after())))
+            };
+
+            let execute = |steps: &[(String, Msg)]| -> Result<(), TestCaseError> {
+                let app = App::default();
+                let contract = setup(&app);
+                check(&app, &contract, None)?;
+
+                for (idx, (sender, msg)) in steps.iter().enumerate() {
+                    // Rejected messages are fine, only the invariants are checked
+                    let _ = app.app_mut().execute_contract(
+                        Addr::unchecked(sender),
+                        contract.clone(),
+                        msg,
+                        &[],
+                    );
+                    check(&app, &contract, Some(idx))?;
+                }
+
+                Ok(())
+            };
+
+            let mut runner = TestRunner::new(config);
+            let result = runner.run(&strategy, |steps| execute(&steps));
+
+            match result {
+                Ok(()) => Ok(()),
+                Err(TestError::Fail(mut reason, mut steps)) => {
+                    // Proptest never retries removing a step once the later ones are shrunk,
+                    // so the steps not needed for the failure are dropped afterwards
+                    let mut idx = 0;
+                    while idx < steps.len() {
+                        let removed = steps.remove(idx);
+                        match execute(&steps) {
+                            Err(TestCaseError::Fail(new_reason)) => reason = new_reason,
+                            _ => {
+                                steps.insert(idx, removed);
+                                idx += 1;
+                            }
+                        }
+                    }
+
+                    Err(FuzzError {
+                        reason: reason.to_string(),
+                        steps,
+                    })
+                }
+                Err(TestError::Abort(reason)) => Err(FuzzError {
+                    reason: reason.to_string(),
+                    steps: vec![],
+                }),
+            }
+        }
+    }
+
+    /// Minimal sequence of messages failing the run
+    #[derive(Debug)]
+    pub struct FuzzError<Msg> {
+        pub reason: String,
+        /// Senders with the messages they sent
+        pub steps: Vec<(String, Msg)>,
+    }
+
+    impl<Msg: Serialize> Display for FuzzError<Msg> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let mut report = format!("{}\nMinimal failing sequence:", self.reason);
+            for (idx, (sender, msg)) in self.steps.iter().enumerate() {
+                let msg = serde_json::to_string(msg).map_err(|_| std::fmt::Error)?;
+                write!(report, "\n  {idx}. {sender}: {msg}")?;
+            }
+            f.write_str(&report)
+        }
+    }
+}
@@ -2,21 +2,39 @@
 //!
 //! Most of implementation lies in `cw-derive-ng` crate which is reexported here
 
+#[cfg(all(feature = "cli", not(target_arch = "wasm32")))]
+pub mod cli;
+pub mod de;
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+pub mod fuzz;
 pub mod into_response;
 #[cfg(feature = "mt")]
 pub mod multitest;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod schema;
 pub mod types;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod typescript;
 pub mod utils;
 
 #[cfg(feature = "mt")]
 pub use anyhow;
+#[cfg(all(feature = "cli", not(target_arch = "wasm32")))]
+pub use clap;
 pub use cosmwasm_std as cw_std;
+#[cfg(all(feature = "proptest", not(target_arch = "wasm32")))]
+pub use proptest;
 #[cfg(feature = "mt")]
 pub use cw_multi_test;
 #[cfg(feature = "mt")]
 pub use derivative;
 pub use schemars;
 pub use serde;
-pub use serde_cw_value as serde_value;
 pub use serde_json_wasm as serde_json;
-pub use sylvia_derive::{contract, entry_points, interface};
+pub use sylvia_derive::{contract, entry_points, import_schema, interface};
+
+/// Deprecated, as glue messages no longer use `serde_cw_value`
+#[deprecated(note = "Glue messages no longer use `serde_cw_value`, depend on it directly instead")]
+pub mod serde_value {
+    pub use serde_cw_value::*;
+}
@@ -1,20 +1,61 @@
-use std::cell::{Ref, RefCell, RefMut};
-use std::fmt::{Debug, Display};
-use std::marker::PhantomData;
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+use std::rc::Rc;
 
 use cosmwasm_std::{
-    Addr, Api, BlockInfo, Coin, CustomQuery, Empty, GovMsg, IbcMsg, IbcQuery, Storage,
+    Addr, Api, CustomQuery, Empty, GovMsg, IbcMsg, IbcQuery, Record, Reply, Storage,
 };
 use cw_multi_test::{
-    Bank, BankKeeper, Distribution, DistributionKeeper, Executor, FailingModule, Gov, Ibc, Module,
-    Router, StakeKeeper, Staking, Wasm, WasmKeeper,
+    BankKeeper, DistributionKeeper, FailingModule, Router, StakeKeeper, WasmKeeper,
 };
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+mod accounts;
+mod bech32;
+mod blocks;
+mod call;
+mod coverage;
+mod custom;
+mod fixture;
+mod invariants;
+mod mock;
+mod proxy;
+mod salt;
+mod storage;
+mod submsgs;
+mod tracked;
+
+pub use self::bech32::{Bech32Api, Bech32App};
+pub use self::blocks::BLOCK_TIME;
+pub use self::call::{CallError, CallResponse};
+pub use self::coverage::{ContractCoverage, Coverage, MessageCoverage};
+pub use self::custom::{CustomMock, CustomMockApp};
+pub use self::fixture::{Deployment, Fixture};
+pub use self::mock::{Mock, MockCall, MockCodeId, MockContract};
+pub use self::proxy::{ExecProxy, MigrateProxy, SudoProxy};
+pub use self::salt::{code_checksum, SaltedAddressGenerator};
+pub use self::storage::{ContractState, KeyChange, Snapshot, StateModel, StorageDiff};
+pub use self::submsgs::{reply_err, reply_ok, DispatchedSubMsg};
+
+type Invariant<MtApp> = Box<dyn Fn(&App<MtApp>) -> anyhow::Result<()>>;
+type DumpStorage<MtApp> = fn(&MtApp) -> Vec<Record>;
+type AdvanceBlock<MtApp> = fn(&mut MtApp);
+/// Contract whose entry point is called first in the current call done through the proxies
+type CallRoot = Rc<RefCell<Option<Addr>>>;
+
 pub struct App<MtApp> {
     app: RefCell<MtApp>,
+    invariants: RefCell<Vec<(String, Invariant<MtApp>)>>,
+    dump_storage: Cell<Option<DumpStorage<MtApp>>>,
+    advance_block: Cell<Option<AdvanceBlock<MtApp>>>,
+    coverage: Rc<RefCell<Coverage>>,
+    submsgs: Rc<RefCell<Option<Vec<DispatchedSubMsg>>>>,
+    injected_reply: Rc<RefCell<Option<(Addr, Reply)>>>,
+    tracked_codes: RefCell<BTreeSet<u64>>,
+    call_root: CallRoot,
 }
 
 impl<MtApp> Default for App<MtApp>
@@ -46,9 +87,7 @@ impl<ExecC, QueryC> App<cw_multi_test::BasicApp<ExecC, QueryC>> {
             &mut dyn Storage,
         ),
     {
-        App {
-            app: RefCell::new(cw_multi_test::custom_app(init_fn)),
-        }
+        Self::new(cw_multi_test::custom_app(init_fn))
     }
 }
 
@@ -56,6 +95,14 @@ impl<MtApp> App<MtApp> {
     pub fn new(app: MtApp) -> Self {
         Self {
             app: RefCell::new(app),
+            invariants: RefCell::new(vec![]),
+            dump_storage: Cell::new(None),
+            advance_block: Cell::new(None),
+            coverage: Rc::default(),
+            submsgs: Rc::default(),
+            injected_reply: Rc::default(),
+            tracked_codes: RefCell::default(),
+            call_root: Rc::default(),
         }
     }
 
@@ -68,121 +115,15 @@ impl<MtApp> App<MtApp> {
     }
 }
 
-impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
-    App<cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>>
-where
-    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
-    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
-    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
-    BankT: Bank,
-    ApiT: Api,
-    StorageT: Storage,
-    CustomT: Module,
-    StakingT: Staking,
-    DistrT: Distribution,
-    IbcT: Ibc,
-    GovT: Gov,
-{
-    pub fn block_info(&self) -> BlockInfo {
-        self.app.borrow().block_info()
-    }
-
-    pub fn set_block(&self, block: BlockInfo) {
-        self.app.borrow_mut().set_block(block)
-    }
-
-    pub fn update_block<F: Fn(&mut BlockInfo)>(&self, action: F) {
-        self.app.borrow_mut().update_block(action)
-    }
-}
-
-#[must_use]
-pub struct ExecProxy<'a, 'app, Error, Msg, MtApp, ExecC>
-where
-    Msg: Serialize + Debug,
-    Error: Debug + Display + Send + Sync + 'static,
-{
-    funds: &'a [Coin],
-    contract_addr: &'a Addr,
-    msg: Msg,
-    app: &'app App<MtApp>,
-    phantom: PhantomData<(Error, ExecC)>,
-}
-
-impl<'a, 'app, Error, Msg, MtApp, ExecC> ExecProxy<'a, 'app, Error, Msg, MtApp, ExecC>
-where
-    Msg: Serialize + Debug,
-    Error: Debug + Display + Send + Sync + 'static,
-    ExecC: Debug + Clone + JsonSchema + PartialEq + 'static,
-    MtApp: Executor<ExecC>,
-{
-    pub fn new(contract_addr: &'a Addr, msg: Msg, app: &'app App<MtApp>) -> Self {
-        Self {
-            funds: &[],
-            contract_addr,
-            msg,
-            app,
-            phantom: PhantomData,
-        }
-    }
-    pub fn with_funds(self, funds: &'a [Coin]) -> Self {
-        Self { funds, ..self }
-    }
-
-    #[track_caller]
-    pub fn call(self, sender: &'a str) -> Result<cw_multi_test::AppResponse, Error> {
-        (*self.app)
-            .app_mut()
-            .execute_contract(
-                Addr::unchecked(sender),
-                Addr::unchecked(self.contract_addr),
-                &self.msg,
-                self.funds,
-            )
-            .map_err(|err| err.downcast().unwrap())
-    }
-}
-
-#[must_use]
-pub struct MigrateProxy<'a, 'app, Error, Msg, MtApp, ExecC>
-where
-    Msg: Serialize + Debug,
-    Error: Debug + Display + Send + Sync + 'static,
-{
-    contract_addr: &'a Addr,
-    msg: Msg,
-    app: &'app App<MtApp>,
-    phantom: PhantomData<(Error, ExecC)>,
+/// Name of the enum message variant serialized to JSON
+fn msg_variant(msg: &[u8]) -> Option<String> {
+    let msg: BTreeMap<String, serde::de::IgnoredAny> = serde_json::from_slice(msg).ok()?;
+    msg.into_keys().next()
 }
 
-impl<'a, 'app, Error, Msg, MtApp, ExecC> MigrateProxy<'a, 'app, Error, Msg, MtApp, ExecC>
-where
-    Msg: Serialize + Debug,
-    Error: Debug + Display + Send + Sync + 'static,
-    ExecC: Debug + Clone + JsonSchema + PartialEq + 'static,
-    MtApp: Executor<ExecC>,
-{
-    pub fn new(contract_addr: &'a Addr, msg: Msg, app: &'app App<MtApp>) -> Self {
-        Self {
-            contract_addr,
-            msg,
-            app,
-            phantom: PhantomData,
-        }
-    }
-
-    #[track_caller]
-    pub fn call(self, sender: &str, new_code_id: u64) -> Result<cw_multi_test::AppResponse, Error> {
-        (*self.app)
-            .app_mut()
-            .migrate_contract(
-                Addr::unchecked(sender),
-                Addr::unchecked(self.contract_addr),
-                &self.msg,
-                new_code_id,
-            )
-            .map_err(|err| err.downcast().unwrap())
-    }
+/// Message as JSON for the reports, falling back to its `Debug` representation
+pub fn report_msg(msg: &(impl Serialize + Debug)) -> String {
+    serde_json::to_string(msg).unwrap_or_else(|_| format!("{msg:?}"))
 }
 
 #[cfg(test)]
@@ -210,6 +151,9 @@ mod tests {
         let basic_app = super::App::new(cw_multi_test::BasicApp::default());
         let custom_app =
             super::App::<cw_multi_test::BasicApp<MyMsg, MyQuery>>::custom(|_, _, _| {});
+        let _ = super::App::<super::CustomMockApp<MyMsg, MyQuery>>::custom_mock(|_, _, _| {});
+        let _ = super::App::<super::Bech32App>::bech32("cosmwasm");
+        let _ = super::App::<super::Bech32App<MyMsg, MyQuery>>::bech32("cosmwasm");
 
         let _: Ref<cw_multi_test::BasicApp> = basic_app.app();
         let _: RefMut<cw_multi_test::BasicApp> = basic_app.app_mut();
@@ -0,0 +1,93 @@
+use cosmwasm_std::{Addr, Api, CanonicalAddr, Coin, CustomQuery, Storage};
+use cw_multi_test::{Bank, BankSudo, Distribution, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use super::App;
+
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    App<cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>>
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Deterministic address of the test account named `name`.
+    ///
+    /// The address is made of the hash of the name by the app `Api`, so with the `Bech32Api`
+    /// it is a valid bech32 address. Panics if the `Api` cannot humanize 32-byte addresses,
+    /// like the default `MockApi`.
+    pub fn account(&self, name: &str) -> Addr {
+        let canonical = CanonicalAddr::from(Sha256::digest(name.as_bytes()).to_vec());
+        self.app
+            .borrow()
+            .read_module(|_, api, _| api.addr_humanize(&canonical))
+            .unwrap_or_else(|err| panic!("Cannot make address of the account `{name}`: {err}"))
+    }
+
+    /// Mints the `amount` to the `addr` balance
+    #[track_caller]
+    pub fn fund(&self, addr: impl Into<String>, amount: &[Coin]) {
+        let to_address = addr.into();
+        self.app
+            .borrow_mut()
+            .sudo(
+                BankSudo::Mint {
+                    to_address: to_address.clone(),
+                    amount: amount.to_vec(),
+                }
+                .into(),
+            )
+            .unwrap_or_else(|err| panic!("Funding {to_address} failed: {err}"));
+    }
+
+    /// Balance of the `addr` in the `denom`
+    #[track_caller]
+    pub fn balance(&self, addr: impl Into<String>, denom: impl Into<String>) -> u128 {
+        let addr = addr.into();
+        self.app
+            .borrow()
+            .wrap()
+            .query_balance(&addr, denom)
+            .unwrap_or_else(|err| panic!("Querying balance of {addr} failed: {err}"))
+            .amount
+            .u128()
+    }
+
+    /// Asserts the `addr` holds exactly the `expected` coins, in any order.
+    ///
+    /// Coins with zero amount are ignored.
+    #[track_caller]
+    pub fn assert_balances(&self, addr: impl Into<String>, expected: &[Coin]) {
+        let addr = addr.into();
+        let balances = self
+            .app
+            .borrow()
+            .wrap()
+            .query_all_balances(&addr)
+            .unwrap_or_else(|err| panic!("Querying balances of {addr} failed: {err}"));
+        let normalize = |coins: &[Coin]| {
+            let mut coins: Vec<_> = coins
+                .iter()
+                .filter(|coin| !coin.amount.is_zero())
+                .cloned()
+                .collect();
+            coins.sort_by(|lhs, rhs| lhs.denom.cmp(&rhs.denom));
+            coins
+        };
+        let (balances, expected) = (normalize(&balances), normalize(expected));
+        assert!(
+            balances == expected,
+            "Balances of {addr} are {balances:?}, expected {expected:?}"
+        );
+    }
+}
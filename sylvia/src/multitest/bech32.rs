@@ -0,0 +1,302 @@
+use std::fmt::Debug;
+
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    Addr, Api, CanonicalAddr, CustomQuery, Empty, RecoverPubkeyError, StdError, StdResult,
+    VerificationError,
+};
+use cw_multi_test::{BankKeeper, FailingModule, WasmKeeper};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use super::{App, SaltedAddressGenerator};
+
+/// `cw_multi_test::App` validating the addresses with the `Bech32Api`
+pub type Bech32App<ExecC = Empty, QueryC = Empty> = cw_multi_test::App<
+    BankKeeper,
+    Bech32Api,
+    MockStorage,
+    FailingModule<ExecC, QueryC, Empty>,
+    WasmKeeper<ExecC, QueryC>,
+>;
+
+impl<ExecC, QueryC> App<Bech32App<ExecC, QueryC>>
+where
+    ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+    QueryC: Debug + CustomQuery + DeserializeOwned + 'static,
+{
+    /// Creates new `App` accepting only the bech32 addresses with the `prefix`.
+    ///
+    /// The app supports instantiating the contracts with a salt by the proxy `with_salt`.
+    pub fn bech32(prefix: impl Into<String>) -> Self {
+        Self::new(
+            cw_multi_test::BasicAppBuilder::<ExecC, QueryC>::new_custom()
+                .with_api(Bech32Api::new(prefix))
+                .with_wasm::<FailingModule<ExecC, QueryC, Empty>, _>(
+                    WasmKeeper::new_with_custom_address_generator(SaltedAddressGenerator),
+                )
+                .build(|_, _, _| {}),
+        )
+    }
+}
+
+/// `Api` validating the addresses like a real chain - as bech32 with the configured prefix.
+///
+/// Contracts instantiated in multitest get the `contract{N}` addresses, which are accepted too.
+/// Crypto functions are the `MockApi` ones.
+#[derive(Clone, Debug)]
+pub struct Bech32Api {
+    prefix: String,
+}
+
+impl Bech32Api {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Deterministic address of the test account named `name`
+    pub fn addr_make(&self, name: &str) -> Addr {
+        Addr::unchecked(encoding::encode(
+            &self.prefix,
+            &Sha256::digest(name.as_bytes()),
+        ))
+    }
+}
+
+fn is_contract_addr(addr: &[u8]) -> bool {
+    addr.strip_prefix(b"contract")
+        .is_some_and(|id| !id.is_empty() && id.iter().all(u8::is_ascii_digit))
+}
+
+impl Api for Bech32Api {
+    fn addr_validate(&self, input: &str) -> StdResult<Addr> {
+        let canonical = self.addr_canonicalize(input)?;
+        let addr = self.addr_humanize(&canonical)?;
+        if addr != input {
+            return Err(StdError::generic_err(format!(
+                "Address `{input}` is not normalized, expected `{addr}`"
+            )));
+        }
+        Ok(addr)
+    }
+
+    fn addr_canonicalize(&self, input: &str) -> StdResult<CanonicalAddr> {
+        if is_contract_addr(input.as_bytes()) {
+            return Ok(CanonicalAddr::from(input.as_bytes()));
+        }
+        let (prefix, data) = encoding::decode(input)
+            .ok_or_else(|| StdError::generic_err(format!("Invalid bech32 address `{input}`")))?;
+        if prefix != self.prefix {
+            return Err(StdError::generic_err(format!(
+                "Address `{input}` has prefix `{prefix}`, expected `{}`",
+                self.prefix
+            )));
+        }
+        Ok(CanonicalAddr::from(data))
+    }
+
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+        if is_contract_addr(canonical.as_slice()) {
+            return Ok(Addr::unchecked(String::from_utf8_lossy(
+                canonical.as_slice(),
+            )));
+        }
+        Ok(Addr::unchecked(encoding::encode(
+            &self.prefix,
+            canonical.as_slice(),
+        )))
+    }
+
+    fn secp256k1_verify(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().secp256k1_verify(message_hash, signature, public_key)
+    }
+
+    fn secp256k1_recover_pubkey(
+        &self,
+        message_hash: &[u8],
+        signature: &[u8],
+        recovery_param: u8,
+    ) -> Result<Vec<u8>, RecoverPubkeyError> {
+        MockApi::default().secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+    }
+
+    fn ed25519_verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        public_key: &[u8],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().ed25519_verify(message, signature, public_key)
+    }
+
+    fn ed25519_batch_verify(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[&[u8]],
+    ) -> Result<bool, VerificationError> {
+        MockApi::default().ed25519_batch_verify(messages, signatures, public_keys)
+    }
+
+    fn debug(&self, message: &str) {
+        MockApi::default().debug(message)
+    }
+}
+
+/// Bech32 encoding as specified in BIP-173
+mod encoding {
+    const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+    const CHECKSUM_LEN: usize = 6;
+
+    fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+        const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+        values.into_iter().fold(1, |chk, value| {
+            let top = chk >> 25;
+            let chk = (chk & 0x1ffffff) << 5 ^ value as u32;
+            GENERATOR
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (top >> i) & 1 == 1)
+                .fold(chk, |chk, (_, generator)| chk ^ generator)
+        })
+    }
+
+    fn expand_prefix(prefix: &str) -> impl Iterator<Item = u8> + '_ {
+        let bytes = prefix.bytes();
+        bytes
+            .clone()
+            .map(|byte| byte >> 5)
+            .chain([0])
+            .chain(bytes.map(|byte| byte & 0x1f))
+    }
+
+    /// Regroups the `bits` wide values into the `to_bits` wide ones
+    fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Option<Vec<u8>> {
+        let max = (1u32 << to_bits) - 1;
+        let (mut acc, mut bits) = (0u32, 0u32);
+        let mut out = vec![];
+        for value in data {
+            acc = (acc << from_bits) | *value as u32;
+            bits += from_bits;
+            while bits >= to_bits {
+                bits -= to_bits;
+                out.push(((acc >> bits) & max) as u8);
+            }
+        }
+        if pad && bits > 0 {
+            out.push(((acc << (to_bits - bits)) & max) as u8);
+        } else if !pad && (bits >= from_bits || (acc << (to_bits - bits)) & max != 0) {
+            return None;
+        }
+        Some(out)
+    }
+
+    pub fn encode(prefix: &str, data: &[u8]) -> String {
+        let data = convert_bits(data, 8, 5, true).unwrap_or_default();
+        let checksum = polymod(
+            expand_prefix(prefix)
+                .chain(data.iter().copied())
+                .chain([0; CHECKSUM_LEN]),
+        ) ^ 1;
+        let checksum =
+            (0..CHECKSUM_LEN).map(|i| ((checksum >> (5 * (CHECKSUM_LEN - 1 - i))) & 0x1f) as u8);
+        let data: String = data
+            .iter()
+            .copied()
+            .chain(checksum)
+            .map(|value| CHARSET[value as usize] as char)
+            .collect();
+        format!("{prefix}1{data}")
+    }
+
+    /// Decodes lowercase bech32 string into its prefix and data
+    pub fn decode(input: &str) -> Option<(String, Vec<u8>)> {
+        if input.len() > 90 || input.bytes().any(|byte| !(33..=126).contains(&byte)) {
+            return None;
+        }
+        if input.to_lowercase() != input {
+            return None;
+        }
+        let (prefix, data) = input.rsplit_once('1')?;
+        if prefix.is_empty() || data.len() < CHECKSUM_LEN {
+            return None;
+        }
+        let data = data
+            .bytes()
+            .map(|byte| CHARSET.iter().position(|c| *c == byte).map(|pos| pos as u8))
+            .collect::<Option<Vec<_>>>()?;
+        if polymod(expand_prefix(prefix).chain(data.iter().copied())) != 1 {
+            return None;
+        }
+        let data = convert_bits(&data[..data.len() - CHECKSUM_LEN], 5, 8, false)?;
+        Some((prefix.to_owned(), data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::Addr;
+
+    #[test]
+    fn bech32() {
+        // Test vectors from BIP-173
+        assert_eq!(
+            super::encoding::decode("a12uel5l"),
+            Some(("a".to_owned(), vec![]))
+        );
+        let (prefix, data) =
+            super::encoding::decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+        assert_eq!(prefix, "abcdef");
+        assert_eq!(
+            super::encoding::encode(&prefix, &data),
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"
+        );
+
+        // Bad checksum, mixed case, no separator, invalid character
+        assert_eq!(super::encoding::decode("a12uel5m"), None);
+        assert_eq!(super::encoding::decode("A12uel5l"), None);
+        assert_eq!(super::encoding::decode("pzry9x0s0muk"), None);
+        assert_eq!(super::encoding::decode("a1b2uel5l"), None);
+    }
+
+    #[test]
+    fn bech32_api() {
+        use cosmwasm_std::Api;
+
+        let api = super::Bech32Api::new("juno");
+        let alice = api.addr_make("alice");
+        assert!(alice.as_str().starts_with("juno1"));
+        assert_eq!(api.addr_make("alice"), alice);
+        assert_ne!(api.addr_make("bob"), alice);
+
+        assert_eq!(api.addr_validate(alice.as_str()).unwrap(), alice);
+        assert_eq!(
+            api.addr_humanize(&api.addr_canonicalize(alice.as_str()).unwrap())
+                .unwrap(),
+            alice
+        );
+        assert_eq!(
+            api.addr_validate("contract3").unwrap(),
+            Addr::unchecked("contract3")
+        );
+
+        let osmo_alice = super::Bech32Api::new("osmo").addr_make("alice");
+        let err = api.addr_validate(osmo_alice.as_str()).unwrap_err();
+        assert!(err.to_string().contains("expected `juno`"));
+        api.addr_validate("alice").unwrap_err();
+        api.addr_validate(&alice.as_str().to_uppercase())
+            .unwrap_err();
+    }
+}
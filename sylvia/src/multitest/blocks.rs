@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use cosmwasm_std::{Api, BlockInfo, CustomQuery, Storage, Timestamp};
+use cw_multi_test::{Bank, Distribution, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use super::{AdvanceBlock, App};
+
+/// Block time of the `App::advance_blocks`, the same as of `cw_multi_test::next_block`
+pub const BLOCK_TIME: Duration = Duration::from_secs(5);
+
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    App<cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>>
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    pub fn block_info(&self) -> BlockInfo {
+        self.app.borrow().block_info()
+    }
+
+    pub fn set_block(&self, block: BlockInfo) {
+        self.app.borrow_mut().set_block(block)
+    }
+
+    pub fn update_block<F: Fn(&mut BlockInfo)>(&self, action: F) {
+        self.app.borrow_mut().update_block(action)
+    }
+
+    /// Moves the chain `blocks` blocks forward, advancing the time by `BLOCK_TIME` per block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block height or the block time in nanoseconds overflows `u64`.
+    pub fn advance_blocks(&self, blocks: u64) {
+        let nanos = blocks
+            .checked_mul(BLOCK_TIME.as_nanos() as u64)
+            .expect("Block time overflows u64 nanoseconds");
+        self.advance_block_by(blocks, nanos)
+    }
+
+    /// Moves the chain time `duration` forward.
+    ///
+    /// The height advances by the number of `BLOCK_TIME` blocks it takes, rounded up, so any
+    /// time passed produces at least one new block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `duration` or the block time in nanoseconds overflows `u64`, or if the
+    /// block height overflows.
+    pub fn advance_time(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).expect("Duration exceeds u64 nanoseconds");
+        let block_nanos = BLOCK_TIME.as_nanos() as u64;
+        // Rounded up division, `u64::div_ceil` requires Rust 1.73
+        let blocks = nanos / block_nanos + u64::from(nanos % block_nanos != 0);
+        self.advance_block_by(blocks, nanos)
+    }
+
+    fn advance_block_by(&self, blocks: u64, nanos: u64) {
+        self.update_block(|block| {
+            block.height = block
+                .height
+                .checked_add(blocks)
+                .expect("Block height overflows u64");
+            let time = block
+                .time
+                .nanos()
+                .checked_add(nanos)
+                .expect("Block time overflows u64 nanoseconds");
+            block.time = Timestamp::from_nanos(time);
+        })
+    }
+
+    /// Enables or disables executing every call through the proxies in a new block.
+    ///
+    /// Before each instantiation, execution, migration and sudo call the chain advances by one
+    /// block, like with `App::advance_blocks(1)`. Queries and simulations do not advance the chain.
+    pub fn auto_advance_blocks(&self, enable: bool) {
+        let advance_block: AdvanceBlock<_> =
+            |app: &mut cw_multi_test::App<_, _, _, _, _, _, _, _, _>| {
+                app.update_block(cw_multi_test::next_block)
+            };
+        self.advance_block.set(enable.then_some(advance_block));
+    }
+}
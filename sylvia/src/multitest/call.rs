@@ -0,0 +1,202 @@
+use std::fmt::{Debug, Display};
+use std::ops::Deref;
+
+use cosmwasm_std::Addr;
+use cw_multi_test::AppResponse;
+
+use super::{App, CallRoot, DispatchedSubMsg, StorageDiff};
+
+impl<MtApp> App<MtApp> {
+    /// Performs the `call` of the proxy, in a new block if `App::auto_advance_blocks` is
+    /// enabled, recording its submessages and its storage changes if enabled.
+    ///
+    /// Errors not returned by any contract, after the called contract is entered, are marked
+    /// as the errors of the messages sent by it.
+    #[doc(hidden)]
+    pub fn proxy_call<T>(
+        &self,
+        call: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<CallResponse<T>> {
+        if let Some(advance_block) = self.advance_block.get() {
+            advance_block(&mut self.app.borrow_mut());
+        }
+
+        let dump_storage = self.dump_storage.get();
+        let before = dump_storage.map(|dump_storage| dump_storage(&self.app.borrow()));
+        *self.submsgs.borrow_mut() = Some(vec![]);
+        self.call_root.take();
+        let response = call();
+        let submsgs = self.submsgs.take().unwrap_or_default();
+        let response = response.map_err(|err| {
+            match (self.call_root.take(), err.downcast_ref::<ContractFailed>()) {
+                (Some(contract), None) => err.context(MessageFailed(contract)),
+                _ => err,
+            }
+        })?;
+        let storage_diff = dump_storage.zip(before).map(|(dump_storage, before)| {
+            StorageDiff::new(&before, &dump_storage(&self.app.borrow()))
+        });
+
+        Ok(CallResponse {
+            response,
+            storage_diff,
+            submsgs,
+        })
+    }
+}
+
+/// Successful call done through the proxies
+#[derive(Clone, Debug)]
+pub struct CallResponse<T = AppResponse> {
+    /// Response of the call, or the contract proxy for the instantiation
+    pub response: T,
+    /// Storage changes done by the call, if recording is enabled with
+    /// `App::record_storage_diffs`
+    pub storage_diff: Option<StorageDiff>,
+    /// Submessages dispatched during the call, in the order they were returned by the contracts
+    /// stored with the generated `CodeId::store_code`.
+    ///
+    /// Submessages of the nested calls are listed too, including the ones of the calls that
+    /// failed afterward.
+    pub submsgs: Vec<DispatchedSubMsg>,
+}
+
+impl<T> CallResponse<T> {
+    /// Replaces the response, keeping the rest of the call details
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> CallResponse<U> {
+        CallResponse {
+            response: f(self.response),
+            storage_diff: self.storage_diff,
+            submsgs: self.submsgs,
+        }
+    }
+}
+
+impl<T> Deref for CallResponse<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.response
+    }
+}
+
+/// Error of the call done through the proxies
+#[derive(Debug)]
+pub enum CallError<E> {
+    /// Error returned by the called contract itself
+    Contract(E),
+    /// Error of the message sent by the called contract, like the error returned by the contract
+    /// it called, with the context of all the calls leading to it
+    Nested(anyhow::Error),
+    /// Error not returned by any contract, like sending funds exceeding the balance
+    Framework(anyhow::Error),
+    /// Invariant broken by the successful call, whose changes are kept
+    Invariant(anyhow::Error),
+}
+
+/// Context added to the errors returned by the contracts entry points
+#[derive(Debug)]
+pub(super) struct ContractFailed {
+    pub(super) contract: Addr,
+    /// The contract is called by another contract, not directly through the proxy
+    nested: bool,
+}
+
+impl ContractFailed {
+    /// Marks the `contract` entering its entry point. The first contract entered in the call is
+    /// the one called through the proxy, any other one is nested.
+    pub(super) fn enter(call_root: &CallRoot, contract: &Addr) -> Self {
+        let mut root = call_root.borrow_mut();
+        let root = root.get_or_insert_with(|| contract.clone());
+        Self {
+            contract: contract.clone(),
+            nested: root != contract,
+        }
+    }
+}
+
+impl Display for ContractFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "contract {} failed", self.contract)
+    }
+}
+
+/// Context added to the errors not returned by any contract, but occurring after the contract
+/// called through the proxy is entered - like the message sent to a non-existing contract
+#[derive(Debug)]
+pub(super) struct MessageFailed(Addr);
+
+impl Display for MessageFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "message sent by contract {} failed", self.0)
+    }
+}
+
+impl<E> CallError<E>
+where
+    E: Debug + Display + Send + Sync + 'static,
+{
+    /// Classifies the error of the message executed on the contract.
+    ///
+    /// Errors returned by the contracts stored with the generated `CodeId::store_code` and by
+    /// the mocks are marked, which tells them apart from the errors of the framework. The mark
+    /// also tells if the contract is the one called through the proxy, or a nested one. Other
+    /// errors occurring after the called contract is entered are marked by the proxies as the
+    /// errors of the messages it sent.
+    pub fn new(err: anyhow::Error) -> Self {
+        match err.downcast_ref::<ContractFailed>() {
+            Some(failed) if failed.nested => Self::Nested(err),
+            Some(_) if err.is::<E>() => Self::Contract(err.downcast().unwrap()),
+            None if err.downcast_ref::<MessageFailed>().is_some() => Self::Nested(err),
+            _ => Self::Framework(err),
+        }
+    }
+
+    /// Error returned by the called contract, `None` for the nested and framework errors
+    pub fn contract(&self) -> Option<&E> {
+        match self {
+            Self::Contract(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Error of the given type anywhere in the chain, like the one returned by a nested contract
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Display + Debug + Send + Sync + 'static,
+    {
+        match self {
+            Self::Contract(err) => (err as &dyn std::any::Any).downcast_ref(),
+            Self::Nested(err) | Self::Framework(err) | Self::Invariant(err) => err.downcast_ref(),
+        }
+    }
+}
+
+impl<E> From<anyhow::Error> for CallError<E>
+where
+    E: Debug + Display + Send + Sync + 'static,
+{
+    fn from(err: anyhow::Error) -> Self {
+        Self::new(err)
+    }
+}
+
+impl<E: Display> Display for CallError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Contract(err) => write!(f, "{err}"),
+            Self::Nested(err) | Self::Framework(err) | Self::Invariant(err) => {
+                write!(f, "{err:#}")
+            }
+        }
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for CallError<E> {}
+
+/// Compares the error returned by the called contract
+impl<E: PartialEq> PartialEq<E> for CallError<E> {
+    fn eq(&self, other: &E) -> bool {
+        matches!(self, Self::Contract(err) if err == other)
+    }
+}
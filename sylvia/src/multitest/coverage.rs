@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::path::Path;
+
+use super::App;
+
+impl<MtApp> App<MtApp> {
+    /// Messages dispatched to the contracts stored with the generated `CodeId::store_code`
+    pub fn coverage(&self) -> Coverage {
+        self.coverage.borrow().clone()
+    }
+
+    /// Writes the coverage report to the file, creating its parent directories.
+    ///
+    /// Reports of several apps can be combined with `Coverage::merge` instead.
+    pub fn write_coverage_report(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.coverage.borrow().write_report(path)
+    }
+}
+
+/// Messages dispatched to the contracts, by contract name
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Coverage {
+    pub contracts: BTreeMap<String, ContractCoverage>,
+}
+
+/// Messages dispatched to a single contract, by message name like `exec transfer` or `migrate`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractCoverage {
+    pub messages: BTreeMap<String, MessageCoverage>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MessageCoverage {
+    pub calls: u64,
+    /// Calls which returned an error
+    pub failures: u64,
+}
+
+impl Coverage {
+    /// Adds the calls of the other coverage, like of the app used in another test
+    pub fn merge(&mut self, other: &Coverage) {
+        for (contract, coverage) in &other.contracts {
+            let messages = &mut self.contracts.entry(contract.clone()).or_default().messages;
+            for (message, other) in &coverage.messages {
+                let message = messages.entry(message.clone()).or_default();
+                message.calls += other.calls;
+                message.failures += other.failures;
+            }
+        }
+    }
+
+    /// Writes the report to the file, creating its parent directories
+    pub fn write_report(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())
+    }
+}
+
+impl ContractCoverage {
+    pub(super) fn register(&mut self, messages: impl IntoIterator<Item = String>) {
+        for message in messages {
+            self.messages.entry(message).or_default();
+        }
+    }
+
+    pub(super) fn record(&mut self, message: String, success: bool) {
+        let message = self.messages.entry(message).or_default();
+        message.calls += 1;
+        if !success {
+            message.failures += 1;
+        }
+    }
+
+    /// Messages never dispatched
+    pub fn untested(&self) -> impl Iterator<Item = &str> {
+        self.messages
+            .iter()
+            .filter(|(_, message)| message.calls == 0)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Messages dispatched, but never failing
+    pub fn errors_not_hit(&self) -> impl Iterator<Item = &str> {
+        self.messages
+            .iter()
+            .filter(|(_, message)| message.calls > 0 && message.failures == 0)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+impl Display for Coverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (contract, coverage) in &self.contracts {
+            let tested = coverage
+                .messages
+                .values()
+                .filter(|msg| msg.calls > 0)
+                .count();
+            writeln!(
+                f,
+                "{contract}: {tested} of {} messages tested",
+                coverage.messages.len()
+            )?;
+
+            let untested: Vec<_> = coverage.untested().collect();
+            if !untested.is_empty() {
+                writeln!(f, "  not tested: {}", untested.join(", "))?;
+            }
+            let errors_not_hit: Vec<_> = coverage.errors_not_hit().collect();
+            if !errors_not_hit.is_empty() {
+                writeln!(f, "  never failed: {}", errors_not_hit.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    Addr, Api, Binary, BlockInfo, CustomQuery, Empty, GovMsg, IbcMsg, IbcQuery, Querier, StdError,
+    StdResult, Storage,
+};
+use cw_multi_test::{
+    AppResponse, Bank, BankKeeper, CosmosRouter, Distribution, DistributionKeeper, FailingModule,
+    Gov, Ibc, Module, Router, StakeKeeper, Staking, Wasm, WasmKeeper,
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{msg_variant, App};
+
+impl<ExecC, QueryC> App<CustomMockApp<ExecC, QueryC>> {
+    /// Creates new `App` recording the custom messages and answering the custom queries with
+    /// the `CustomMock`
+    pub fn custom_mock<F>(init_fn: F) -> Self
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + Serialize + DeserializeOwned + 'static,
+        QueryC: Debug + CustomQuery + DeserializeOwned + 'static,
+        F: FnOnce(
+            &mut Router<
+                BankKeeper,
+                CustomMock<ExecC, QueryC>,
+                WasmKeeper<ExecC, QueryC>,
+                StakeKeeper,
+                DistributionKeeper,
+                FailingModule<IbcMsg, IbcQuery, Empty>,
+                FailingModule<GovMsg, Empty, Empty>,
+            >,
+            &dyn Api,
+            &mut dyn Storage,
+        ),
+    {
+        Self::new(
+            cw_multi_test::BasicAppBuilder::<ExecC, QueryC>::new_custom()
+                .with_custom(CustomMock::default())
+                .build(init_fn),
+        )
+    }
+}
+
+/// Storage key of the custom messages recorded by the `CustomMock`, in its own namespace
+const CUSTOM_MOCK_SENT_KEY: &[u8] = b"\0\x0bcustom_mocksent";
+
+type CustomQueryHandler<QueryC> = Box<dyn Fn(QueryC) -> StdResult<Binary>>;
+
+/// `cw_multi_test::App` handling the custom messages with the `CustomMock`
+pub type CustomMockApp<ExecC = Empty, QueryC = Empty> = cw_multi_test::App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    CustomMock<ExecC, QueryC>,
+    WasmKeeper<ExecC, QueryC>,
+>;
+
+/// Multitest module standing for the chain handling custom messages and queries.
+///
+/// Every custom message sent by the contracts succeeds and is recorded in the chain storage, so
+/// the messages sent by the failed transactions are dropped with their other changes. Custom
+/// queries are answered by the handlers registered per query variant, and fail if there is none.
+pub struct CustomMock<ExecC = Empty, QueryC = Empty> {
+    queries: BTreeMap<String, CustomQueryHandler<QueryC>>,
+    phantom: PhantomData<ExecC>,
+}
+
+impl<ExecC, QueryC> Default for CustomMock<ExecC, QueryC> {
+    fn default() -> Self {
+        Self {
+            queries: BTreeMap::new(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<ExecC, QueryC> CustomMock<ExecC, QueryC> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers the custom query variant, like `count`, with the `handler`
+    pub fn on_query<R: Serialize>(
+        &mut self,
+        variant: &str,
+        handler: impl Fn(QueryC) -> StdResult<R> + 'static,
+    ) -> &mut Self {
+        self.queries.insert(
+            variant.to_owned(),
+            Box::new(move |query| cosmwasm_std::to_binary(&handler(query)?)),
+        );
+        self
+    }
+
+    /// Answers the custom query variant always with the same `response`
+    pub fn query_returns(&mut self, variant: &str, response: &impl Serialize) -> &mut Self {
+        let response = cosmwasm_std::to_binary(response).expect("Mocked response not serializable");
+        self.queries
+            .insert(variant.to_owned(), Box::new(move |_| Ok(response.clone())));
+        self
+    }
+
+    /// Custom messages sent so far, read from the chain `storage`
+    pub fn sent(&self, storage: &dyn Storage) -> Vec<ExecC>
+    where
+        ExecC: DeserializeOwned,
+    {
+        storage
+            .get(CUSTOM_MOCK_SENT_KEY)
+            .map(|sent| cosmwasm_std::from_slice(&sent).expect("Invalid custom messages stored"))
+            .unwrap_or_default()
+    }
+}
+
+impl<ExecC, QueryC> Module for CustomMock<ExecC, QueryC>
+where
+    ExecC: Serialize + DeserializeOwned,
+    QueryC: Serialize,
+{
+    type ExecT = ExecC;
+    type QueryT = QueryC;
+    type SudoT = Empty;
+
+    fn execute<ExecCT, QueryCT>(
+        &self,
+        _api: &dyn Api,
+        storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecCT, QueryC = QueryCT>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        msg: Self::ExecT,
+    ) -> anyhow::Result<AppResponse>
+    where
+        ExecCT: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryCT: CustomQuery + DeserializeOwned + 'static,
+    {
+        let mut sent = self.sent(storage);
+        sent.push(msg);
+        storage.set(CUSTOM_MOCK_SENT_KEY, &cosmwasm_std::to_vec(&sent)?);
+        Ok(AppResponse::default())
+    }
+
+    fn sudo<ExecCT, QueryCT>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecCT, QueryC = QueryCT>,
+        _block: &BlockInfo,
+        _msg: Self::SudoT,
+    ) -> anyhow::Result<AppResponse>
+    where
+        ExecCT: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryCT: CustomQuery + DeserializeOwned + 'static,
+    {
+        Ok(AppResponse::default())
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Self::QueryT,
+    ) -> anyhow::Result<Binary> {
+        let variant = serde_json::to_vec(&request)
+            .ok()
+            .and_then(|query| msg_variant(&query))
+            .unwrap_or_default();
+        let handler = self.queries.get(&variant).ok_or_else(|| {
+            StdError::generic_err(format!("No response mocked for `{variant}` custom query"))
+        })?;
+        Ok(handler(request)?)
+    }
+}
+
+impl<BankT, ApiT, StorageT, ExecC, QueryC, WasmT, StakingT, DistrT, IbcT, GovT>
+    App<
+        cw_multi_test::App<
+            BankT,
+            ApiT,
+            StorageT,
+            CustomMock<ExecC, QueryC>,
+            WasmT,
+            StakingT,
+            DistrT,
+            IbcT,
+            GovT,
+        >,
+    >
+where
+    ExecC: Debug + PartialEq + Clone + JsonSchema + Serialize + DeserializeOwned + 'static,
+    QueryC: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<ExecC, QueryC>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Custom messages sent by the contracts so far.
+    ///
+    /// The messages are kept in the chain storage, so the ones sent by the failed transactions
+    /// are not listed, and they are rolled back by `App::restore` and `ExecProxy::simulate`.
+    pub fn custom_msgs_sent(&self) -> Vec<ExecC> {
+        self.app
+            .borrow()
+            .read_module(|router, _, storage| router.custom.sent(storage))
+    }
+
+    /// Answers the custom query variant, like `count`, with the `handler`
+    pub fn on_custom_query<R: Serialize>(
+        &self,
+        variant: &str,
+        handler: impl Fn(QueryC) -> StdResult<R> + 'static,
+    ) {
+        self.app.borrow_mut().init_modules(|router, _, _| {
+            router.custom.on_query(variant, handler);
+        })
+    }
+
+    /// Answers the custom query variant always with the same `response`
+    pub fn custom_query_returns(&self, variant: &str, response: &impl Serialize) {
+        self.app.borrow_mut().init_modules(|router, _, _| {
+            router.custom.query_returns(variant, response);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, Addr, BankMsg, CosmosMsg, CustomMsg, CustomQuery, QueryRequest};
+    use cw_multi_test::Executor;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+    struct MyMsg;
+
+    impl CustomMsg for MyMsg {}
+
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
+    struct MyQuery;
+
+    impl CustomQuery for MyQuery {}
+
+    #[test]
+    fn custom_mock() {
+        let app = super::App::<super::CustomMockApp<MyMsg, MyQuery>>::custom_mock(|_, _, _| {});
+        assert!(app.custom_msgs_sent().is_empty());
+
+        // Messages sent by the failed transaction are dropped
+        let sender = Addr::unchecked("sender");
+        let send = BankMsg::Send {
+            to_address: "recipient".to_owned(),
+            amount: coins(1, "uatom"),
+        };
+        app.app_mut()
+            .execute_multi(sender.clone(), vec![CosmosMsg::Custom(MyMsg), send.into()])
+            .unwrap_err();
+        assert!(app.custom_msgs_sent().is_empty());
+
+        app.app_mut()
+            .execute(sender, CosmosMsg::Custom(MyMsg))
+            .unwrap();
+        assert_eq!(app.custom_msgs_sent(), [MyMsg]);
+
+        let err = app
+            .app()
+            .wrap()
+            .query::<u32>(&QueryRequest::Custom(MyQuery))
+            .unwrap_err();
+        assert!(err.to_string().contains("No response mocked for"));
+
+        // Unit struct query has no variant name
+        app.custom_query_returns("", &5);
+        let resp: u32 = app
+            .app()
+            .wrap()
+            .query(&QueryRequest::Custom(MyQuery))
+            .unwrap();
+        assert_eq!(resp, 5);
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::{Debug, Display};
+
+use cosmwasm_std::{Addr, Api, Coin, CustomQuery, Storage};
+use cw_multi_test::{Bank, Distribution, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use super::{App, CallError};
+
+type Deploy<'app, MtApp> = Box<dyn FnOnce(&Deployment<'app, MtApp>) -> anyhow::Result<Addr> + 'app>;
+type Fund<MtApp> = Box<dyn FnOnce(&App<MtApp>)>;
+
+/// Declarative setup of the contracts and balances the test starts with.
+///
+/// The contracts are deployed in the declaration order on `Fixture::build`, so the ones
+/// declared later can use the addresses of the former, like to pass them in the instantiate
+/// message.
+///
+/// ```ignore
+/// let (vault, forwarder) = Fixture::new(&app)
+///     .fund("owner", &coins(100, "ujuno"))
+///     .contract("vault", |_| VaultCodeId::store_code(&app).instantiate().call("owner"))
+///     .contract("forwarder", |deployment| {
+///         ForwarderCodeId::store_code(&app)
+///             .instantiate(deployment.addr("vault").into_string())
+///             .call("owner")
+///     })
+///     .build(|deployment| (deployment.proxy("vault"), deployment.proxy("forwarder")));
+/// ```
+#[must_use]
+pub struct Fixture<'app, MtApp> {
+    app: &'app App<MtApp>,
+    balances: Vec<Fund<MtApp>>,
+    contracts: Vec<(String, Deploy<'app, MtApp>)>,
+}
+
+impl<'app, MtApp> Fixture<'app, MtApp> {
+    pub fn new(app: &'app App<MtApp>) -> Self {
+        Self {
+            app,
+            balances: vec![],
+            contracts: vec![],
+        }
+    }
+
+    /// Declares the contract deployed by the `deploy` under the `name`.
+    ///
+    /// The `deploy` is called with the contracts declared before, and returns the instantiated
+    /// contract, like the generated `InstantiateProxy::call` or the `MockCodeId::instantiate`.
+    #[track_caller]
+    pub fn contract<Contract, Error>(
+        mut self,
+        name: &str,
+        deploy: impl FnOnce(&Deployment<'app, MtApp>) -> Result<Contract, CallError<Error>> + 'app,
+    ) -> Self
+    where
+        Contract: Into<Addr>,
+        Error: Debug + Display + Send + Sync + 'static,
+    {
+        if self.contracts.iter().any(|(declared, _)| declared == name) {
+            panic!("Contract `{name}` is already declared in the fixture");
+        }
+        let deploy: Deploy<'app, MtApp> =
+            Box::new(move |deployment| Ok(deploy(deployment).map(Into::into)?));
+        self.contracts.push((name.to_owned(), deploy));
+        self
+    }
+
+    /// Funds the accounts and deploys the contracts, passing the deployment to the `contracts`
+    /// to build the typed proxies of the test from.
+    ///
+    /// Panics if any of the contracts fails to instantiate.
+    #[track_caller]
+    pub fn build<T>(self, contracts: impl FnOnce(&Deployment<'app, MtApp>) -> T) -> T {
+        for fund in self.balances {
+            fund(self.app);
+        }
+
+        let mut deployment = Deployment {
+            app: self.app,
+            contracts: vec![],
+        };
+        for (name, deploy) in self.contracts {
+            match deploy(&deployment) {
+                Ok(addr) => deployment.contracts.push((name, addr)),
+                Err(err) => panic!("Deploying `{name}` failed: {err}"),
+            }
+        }
+
+        contracts(&deployment)
+    }
+}
+
+impl<'app, BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    Fixture<
+        'app,
+        cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>,
+    >
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Funds the `addr` with the `amount` before any contract is deployed
+    pub fn fund(mut self, addr: impl Into<String>, amount: &[Coin]) -> Self {
+        let addr = addr.into();
+        let amount = amount.to_vec();
+        self.balances
+            .push(Box::new(move |app: &App<_>| app.fund(addr, &amount)));
+        self
+    }
+}
+
+/// Contracts deployed by the `Fixture` so far
+pub struct Deployment<'app, MtApp> {
+    app: &'app App<MtApp>,
+    contracts: Vec<(String, Addr)>,
+}
+
+impl<'app, MtApp> Deployment<'app, MtApp> {
+    pub fn app(&self) -> &'app App<MtApp> {
+        self.app
+    }
+
+    /// Address of the contract deployed under the `name`
+    #[track_caller]
+    pub fn addr(&self, name: &str) -> Addr {
+        match self.contracts.iter().find(|(deployed, _)| deployed == name) {
+            Some((_, addr)) => addr.clone(),
+            None => panic!(
+                "Contract `{name}` is not deployed yet, the contracts are deployed in the declaration order"
+            ),
+        }
+    }
+
+    /// Proxy of the contract deployed under the `name`, like the generated `ContractProxy`
+    #[track_caller]
+    pub fn proxy<Proxy>(&self, name: &str) -> Proxy
+    where
+        Proxy: From<(Addr, &'app App<MtApp>)>,
+    {
+        Proxy::from((self.addr(name), self.app))
+    }
+}
@@ -0,0 +1,47 @@
+use super::App;
+
+impl<MtApp> App<MtApp> {
+    /// Registers invariant checked after every successful instantiation, execution and migration
+    /// done through the proxies. The broken invariant fails such call with the
+    /// `CallError::Invariant`, while the changes done by the call are kept.
+    ///
+    /// Contracts are queried through their proxies created with the passed `App`. Generated
+    /// contract proxies provide the `invariant` method doing so for a single contract.
+    pub fn add_invariant(
+        &self,
+        name: impl Into<String>,
+        invariant: impl Fn(&App<MtApp>) -> anyhow::Result<()> + 'static,
+    ) {
+        self.invariants
+            .borrow_mut()
+            .push((name.into(), Box::new(invariant)));
+    }
+
+    /// Checks all registered invariants, failing with the first broken one
+    pub fn check_invariants(&self) -> anyhow::Result<()> {
+        self.invariants
+            .borrow()
+            .iter()
+            .try_for_each(|(name, invariant)| {
+                invariant(self).map_err(|err| anyhow::anyhow!("Invariant `{name}` broken: {err:#}"))
+            })
+    }
+
+    /// Checks all registered invariants, describing the call after which they are checked in the
+    /// error
+    #[doc(hidden)]
+    pub fn check_invariants_after(&self, action: impl FnOnce() -> String) -> anyhow::Result<()> {
+        self.check_invariants()
+            .map_err(|err| anyhow::anyhow!("{err}\nAfter {}", action()))
+    }
+
+    /// Panics with the report if any of the registered invariants is broken.
+    ///
+    /// The `action` describes the call after which the invariants are checked.
+    #[track_caller]
+    pub fn assert_invariants(&self, action: impl FnOnce() -> String) {
+        if let Err(err) = self.check_invariants_after(action) {
+            panic!("{err}");
+        }
+    }
+}
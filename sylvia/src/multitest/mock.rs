@@ -0,0 +1,337 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use anyhow::Context;
+use cosmwasm_std::{
+    Addr, Api, Binary, Coin, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
+    StdError, StdResult, Storage,
+};
+use cw_multi_test::{
+    Bank, Contract, Distribution, Executor, Gov, Ibc, Module, Staking, Wasm, WasmKeeper,
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::types::{ExecCtx, QueryCtx};
+
+use super::call::ContractFailed;
+use super::{msg_variant, App, CallError, CallRoot};
+
+type MockExec<ExecMsg, ExecC, QueryC> =
+    Box<dyn Fn(ExecCtx<QueryC>, ExecMsg) -> StdResult<Response<ExecC>>>;
+type MockQuery<QueryMsg, QueryC> = Box<dyn Fn(QueryCtx<QueryC>, QueryMsg) -> StdResult<Binary>>;
+type MockCalls<ExecMsg> = Rc<RefCell<Vec<MockCall<ExecMsg>>>>;
+
+/// Contract standing for an interface implementation in multitest.
+///
+/// Messages are handled by the handlers registered per message variant. Execution messages
+/// without a handler succeed with an empty response, while queries without one fail.
+/// Every `#[interface]` generates the `trait_utils::<Interface>Mock` wrapping it with the
+/// handlers taking the messages fields as typed arguments.
+pub struct Mock<ExecMsg, QueryMsg, ExecC = Empty, QueryC: CustomQuery = Empty> {
+    exec: BTreeMap<String, MockExec<ExecMsg, ExecC, QueryC>>,
+    query: BTreeMap<String, MockQuery<QueryMsg, QueryC>>,
+    calls: MockCalls<ExecMsg>,
+    call_root: CallRoot,
+}
+
+/// Execution message received by the `Mock`
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockCall<ExecMsg> {
+    pub contract: Addr,
+    pub sender: Addr,
+    pub funds: Vec<Coin>,
+    pub msg: ExecMsg,
+}
+
+impl<ExecMsg, QueryMsg, ExecC, QueryC: CustomQuery> Default
+    for Mock<ExecMsg, QueryMsg, ExecC, QueryC>
+{
+    fn default() -> Self {
+        Self {
+            exec: BTreeMap::new(),
+            query: BTreeMap::new(),
+            calls: Rc::default(),
+            call_root: Rc::default(),
+        }
+    }
+}
+
+impl<ExecMsg, QueryMsg, ExecC, QueryC: CustomQuery> Mock<ExecMsg, QueryMsg, ExecC, QueryC> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handles the execution message variant, like `update_admins`, with the `handler`
+    pub fn on_exec(
+        mut self,
+        variant: &str,
+        handler: impl Fn(ExecCtx<QueryC>, ExecMsg) -> StdResult<Response<ExecC>> + 'static,
+    ) -> Self {
+        self.exec.insert(variant.to_owned(), Box::new(handler));
+        self
+    }
+
+    /// Responds to the execution message variant always with the same `response`
+    pub fn exec_returns(self, variant: &str, response: Response<ExecC>) -> Self
+    where
+        ExecC: Clone + 'static,
+    {
+        self.on_exec(variant, move |_, _| Ok(response.clone()))
+    }
+
+    /// Handles the query message variant, like `admin_list`, with the `handler`
+    pub fn on_query(
+        mut self,
+        variant: &str,
+        handler: impl Fn(QueryCtx<QueryC>, QueryMsg) -> StdResult<Binary> + 'static,
+    ) -> Self {
+        self.query.insert(variant.to_owned(), Box::new(handler));
+        self
+    }
+
+    /// Responds to the query message variant always with the same `response`
+    pub fn query_returns(self, variant: &str, response: &impl Serialize) -> Self {
+        let response = cosmwasm_std::to_binary(response).expect("Mocked response not serializable");
+        self.on_query(variant, move |_, _| Ok(response.clone()))
+    }
+
+    /// Stores the mock in the `App`. Every contract instantiated from it shares its handlers.
+    #[allow(clippy::type_complexity)]
+    pub fn store_code<'app, BankT, ApiT, StorageT, CustomT, StakingT, DistrT, IbcT, GovT>(
+        self,
+        app: &'app App<
+            cw_multi_test::App<
+                BankT,
+                ApiT,
+                StorageT,
+                CustomT,
+                WasmKeeper<ExecC, QueryC>,
+                StakingT,
+                DistrT,
+                IbcT,
+                GovT,
+            >,
+        >,
+    ) -> MockCodeId<
+        'app,
+        ExecMsg,
+        cw_multi_test::App<
+            BankT,
+            ApiT,
+            StorageT,
+            CustomT,
+            WasmKeeper<ExecC, QueryC>,
+            StakingT,
+            DistrT,
+            IbcT,
+            GovT,
+        >,
+    >
+    where
+        ExecMsg: DeserializeOwned + Clone + 'static,
+        QueryMsg: DeserializeOwned + 'static,
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+        BankT: Bank,
+        ApiT: Api,
+        StorageT: Storage,
+        CustomT: Module<ExecT = ExecC, QueryT = QueryC>,
+        StakingT: Staking,
+        DistrT: Distribution,
+        IbcT: Ibc,
+        GovT: Gov,
+    {
+        let calls = self.calls.clone();
+        let mock = Self {
+            call_root: app.call_root.clone(),
+            ..self
+        };
+        let code_id = app.app_mut().store_code(Box::new(mock));
+        MockCodeId {
+            code_id,
+            app,
+            calls,
+        }
+    }
+}
+
+impl<ExecMsg, QueryMsg, ExecC, QueryC> Contract<ExecC, QueryC>
+    for Mock<ExecMsg, QueryMsg, ExecC, QueryC>
+where
+    ExecMsg: DeserializeOwned + Clone,
+    QueryMsg: DeserializeOwned,
+    ExecC: Clone + Debug + PartialEq + JsonSchema,
+    QueryC: CustomQuery,
+{
+    fn execute(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        let failed = ContractFailed::enter(&self.call_root, &env.contract.address);
+        let variant = msg_variant(&msg).unwrap_or_default();
+        let msg: ExecMsg = match cosmwasm_std::from_slice(&msg) {
+            Ok(msg) => msg,
+            Err(err) => return Err(err).context(failed),
+        };
+        self.calls.borrow_mut().push(MockCall {
+            contract: failed.contract.clone(),
+            sender: info.sender.clone(),
+            funds: info.funds.clone(),
+            msg: msg.clone(),
+        });
+
+        match self.exec.get(&variant) {
+            Some(handler) => handler((deps, env, info).into(), msg),
+            None => Ok(Response::new()),
+        }
+        .context(failed)
+    }
+
+    fn instantiate(
+        &self,
+        _deps: DepsMut<QueryC>,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        Ok(Response::new())
+    }
+
+    fn query(&self, deps: Deps<QueryC>, env: Env, msg: Vec<u8>) -> anyhow::Result<Binary> {
+        let variant = msg_variant(&msg).unwrap_or_default();
+        let handler = self.query.get(&variant).ok_or_else(|| {
+            StdError::generic_err(format!("No response mocked for `{variant}` query"))
+        })?;
+        Ok(handler(
+            (deps, env).into(),
+            cosmwasm_std::from_slice(&msg)?,
+        )?)
+    }
+
+    fn sudo(
+        &self,
+        _deps: DepsMut<QueryC>,
+        _env: Env,
+        _msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        anyhow::bail!("Sudo is not supported by mocks")
+    }
+
+    fn reply(
+        &self,
+        _deps: DepsMut<QueryC>,
+        _env: Env,
+        _msg: Reply,
+    ) -> anyhow::Result<Response<ExecC>> {
+        anyhow::bail!("Reply is not supported by mocks")
+    }
+
+    fn migrate(
+        &self,
+        _deps: DepsMut<QueryC>,
+        _env: Env,
+        _msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        anyhow::bail!("Migrate is not supported by mocks")
+    }
+}
+
+/// `Mock` stored in the `App`
+pub struct MockCodeId<'app, ExecMsg, MtApp> {
+    code_id: u64,
+    app: &'app App<MtApp>,
+    calls: MockCalls<ExecMsg>,
+}
+
+impl<'app, ExecMsg, BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    MockCodeId<
+        'app,
+        ExecMsg,
+        cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>,
+    >
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    pub fn code_id(&self) -> u64 {
+        self.code_id
+    }
+
+    /// Instantiates the mock. Any instantiation message is accepted.
+    #[allow(clippy::type_complexity)]
+    pub fn instantiate(
+        &self,
+        sender: &str,
+    ) -> Result<
+        MockContract<
+            'app,
+            ExecMsg,
+            cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>,
+        >,
+        CallError<StdError>,
+    > {
+        let contract_addr = self
+            .app
+            .app_mut()
+            .instantiate_contract(
+                self.code_id,
+                Addr::unchecked(sender),
+                &Empty {},
+                &[],
+                "Mock",
+                None,
+            )
+            .map_err(CallError::new)?;
+
+        Ok(MockContract {
+            contract_addr,
+            app: self.app,
+            calls: self.calls.clone(),
+        })
+    }
+}
+
+/// Instance of the `Mock`
+pub struct MockContract<'app, ExecMsg, MtApp> {
+    pub contract_addr: Addr,
+    pub app: &'app App<MtApp>,
+    calls: MockCalls<ExecMsg>,
+}
+
+impl<ExecMsg, MtApp> MockContract<'_, ExecMsg, MtApp>
+where
+    ExecMsg: Clone,
+{
+    /// Execution messages received by this contract so far, including the failed ones
+    pub fn calls(&self) -> Vec<MockCall<ExecMsg>> {
+        self.calls
+            .borrow()
+            .iter()
+            .filter(|call| call.contract == self.contract_addr)
+            .cloned()
+            .collect()
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl<ExecMsg, MtApp> Into<Addr> for MockContract<'_, ExecMsg, MtApp> {
+    fn into(self) -> Addr {
+        self.contract_addr
+    }
+}
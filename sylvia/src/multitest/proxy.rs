@@ -0,0 +1,282 @@
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+
+use cosmwasm_std::{Addr, Api, Coin, CustomQuery, Storage};
+use cw_multi_test::{Bank, Distribution, Executor, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::{report_msg, App, CallError, CallResponse};
+
+#[must_use]
+pub struct ExecProxy<'a, 'app, Error, Msg, MtApp, ExecC>
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+{
+    funds: &'a [Coin],
+    contract_addr: &'a Addr,
+    msg: Msg,
+    app: &'app App<MtApp>,
+    phantom: PhantomData<(Error, ExecC)>,
+}
+
+impl<'a, 'app, Error, Msg, MtApp, ExecC> ExecProxy<'a, 'app, Error, Msg, MtApp, ExecC>
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+    ExecC: Debug + Clone + JsonSchema + PartialEq + 'static,
+    MtApp: Executor<ExecC>,
+{
+    pub fn new(contract_addr: &'a Addr, msg: Msg, app: &'app App<MtApp>) -> Self {
+        Self {
+            funds: &[],
+            contract_addr,
+            msg,
+            app,
+            phantom: PhantomData,
+        }
+    }
+    pub fn with_funds(self, funds: &'a [Coin]) -> Self {
+        Self { funds, ..self }
+    }
+
+    #[track_caller]
+    pub fn call(self, sender: &'a str) -> Result<cw_multi_test::AppResponse, CallError<Error>> {
+        self.call_with_response(sender)
+            .map(|response| response.response)
+    }
+
+    /// Executes the message like `call`, returning the response with the details of the call
+    #[track_caller]
+    pub fn call_with_response(self, sender: &'a str) -> Result<CallResponse, CallError<Error>> {
+        self.execute(sender)
+    }
+
+    #[track_caller]
+    fn execute(&self, sender: &str) -> Result<CallResponse, CallError<Error>> {
+        let response = self
+            .app
+            .proxy_call(|| {
+                (*self.app).app_mut().execute_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(self.contract_addr),
+                    &self.msg,
+                    self.funds,
+                )
+            })
+            .map_err(CallError::new)?;
+
+        self.app
+            .check_invariants_after(|| {
+                format!(
+                    "execution on {} by {sender} with funds {:?}\nMessage: {}\nResponse: {:?}",
+                    self.contract_addr,
+                    self.funds,
+                    report_msg(&self.msg),
+                    response.response,
+                )
+            })
+            .map_err(CallError::Invariant)?;
+        Ok(response)
+    }
+}
+
+impl<'a, 'app, Error, Msg, BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    ExecProxy<
+        'a,
+        'app,
+        Error,
+        Msg,
+        cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>,
+        CustomT::ExecT,
+    >
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Executes the message the same way as `call_with_response`, but discards all the state
+    /// changes afterward, the same way as `App::restore`.
+    ///
+    /// The storage and the block are reverted, so is the new block the execution is done in
+    /// with `App::auto_advance_blocks`. The records of what the test exercised are kept - the
+    /// simulated message is counted in the `App::coverage` and listed in `MockContract::calls`,
+    /// like the failed ones.
+    ///
+    /// The proxy is not consumed, so the same message can be simulated and then called.
+    #[track_caller]
+    pub fn simulate(&self, sender: &str) -> Result<CallResponse, CallError<Error>> {
+        let snapshot = self.app.snapshot();
+        let response = self.execute(sender);
+        self.app.restore(&snapshot);
+        response
+    }
+}
+
+#[must_use]
+pub struct MigrateProxy<'a, 'app, Error, Msg, MtApp, ExecC>
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+{
+    contract_addr: &'a Addr,
+    msg: Msg,
+    app: &'app App<MtApp>,
+    phantom: PhantomData<(Error, ExecC)>,
+}
+
+impl<'a, 'app, Error, Msg, MtApp, ExecC> MigrateProxy<'a, 'app, Error, Msg, MtApp, ExecC>
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+    ExecC: Debug + Clone + JsonSchema + PartialEq + 'static,
+    MtApp: Executor<ExecC>,
+{
+    pub fn new(contract_addr: &'a Addr, msg: Msg, app: &'app App<MtApp>) -> Self {
+        Self {
+            contract_addr,
+            msg,
+            app,
+            phantom: PhantomData,
+        }
+    }
+
+    #[track_caller]
+    pub fn call(
+        self,
+        sender: &str,
+        new_code_id: u64,
+    ) -> Result<cw_multi_test::AppResponse, CallError<Error>> {
+        self.call_with_response(sender, new_code_id)
+            .map(|response| response.response)
+    }
+
+    /// Migrates the contract like `call`, returning the response with the details of the call
+    #[track_caller]
+    pub fn call_with_response(
+        self,
+        sender: &str,
+        new_code_id: u64,
+    ) -> Result<CallResponse, CallError<Error>> {
+        let response = self
+            .app
+            .proxy_call(|| {
+                (*self.app).app_mut().migrate_contract(
+                    Addr::unchecked(sender),
+                    Addr::unchecked(self.contract_addr),
+                    &self.msg,
+                    new_code_id,
+                )
+            })
+            .map_err(CallError::new)?;
+
+        self.app
+            .check_invariants_after(|| {
+                format!(
+                    "migration of {} to code {new_code_id} by {sender}\nMessage: {}\nResponse: {:?}",
+                    self.contract_addr,
+                    report_msg(&self.msg),
+                    response.response,
+                )
+            })
+            .map_err(CallError::Invariant)?;
+        Ok(response)
+    }
+}
+
+#[must_use]
+pub struct SudoProxy<'a, 'app, Error, Msg, MtApp>
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+{
+    contract_addr: &'a Addr,
+    msg: Msg,
+    app: &'app App<MtApp>,
+    phantom: PhantomData<Error>,
+}
+
+impl<'a, 'app, Error, Msg, MtApp> SudoProxy<'a, 'app, Error, Msg, MtApp>
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+{
+    pub fn new(contract_addr: &'a Addr, msg: Msg, app: &'app App<MtApp>) -> Self {
+        Self {
+            contract_addr,
+            msg,
+            app,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'app, Error, Msg, BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    SudoProxy<
+        'a,
+        'app,
+        Error,
+        Msg,
+        cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>,
+    >
+where
+    Msg: Serialize + Debug,
+    Error: Debug + Display + Send + Sync + 'static,
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Calls the `sudo` entry point of the contract, like the chain governance would.
+    ///
+    /// There is no sender of the sudo message, so no funds can be sent with it.
+    #[track_caller]
+    pub fn call(self) -> Result<cw_multi_test::AppResponse, CallError<Error>> {
+        self.call_with_response().map(|response| response.response)
+    }
+
+    /// Calls the `sudo` entry point like `call`, returning the response with the details of the
+    /// call
+    #[track_caller]
+    pub fn call_with_response(self) -> Result<CallResponse, CallError<Error>> {
+        let response = self
+            .app
+            .proxy_call(|| {
+                (*self.app)
+                    .app_mut()
+                    .wasm_sudo(self.contract_addr.clone(), &self.msg)
+            })
+            .map_err(CallError::new)?;
+
+        self.app
+            .check_invariants_after(|| {
+                format!(
+                    "sudo on {}\nMessage: {}\nResponse: {:?}",
+                    self.contract_addr,
+                    report_msg(&self.msg),
+                    response.response,
+                )
+            })
+            .map_err(CallError::Invariant)?;
+        Ok(response)
+    }
+}
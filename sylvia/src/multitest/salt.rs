@@ -0,0 +1,112 @@
+use cosmwasm_std::{
+    instantiate2_address, Addr, Api, CustomQuery, Order, StdError, StdResult, Storage,
+};
+use cw_multi_test::{AddressGenerator, Bank, Distribution, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+
+use super::storage::wasm_storage;
+use super::App;
+
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    App<cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>>
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Address of the contract instantiated from the `code_id` by the `creator` with the `salt`.
+    ///
+    /// The address follows the instantiate2 semantics, with the `code_checksum` as the checksum
+    /// of the code. The app `Api` has to humanize 32-byte addresses, like the `Bech32Api`.
+    ///
+    /// Only the contracts instantiated by the proxy `with_salt` get such addresses - the
+    /// `WasmKeeper` does not support the `WasmMsg::Instantiate2` sent by the contracts.
+    pub fn predict_address(&self, code_id: u64, creator: &str, salt: &[u8]) -> StdResult<Addr> {
+        self.app.borrow().read_module(|_, api, _| {
+            let creator = api.addr_canonicalize(creator)?;
+            let addr = instantiate2_address(&code_checksum(code_id), &creator, salt)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            api.addr_humanize(&addr)
+        })
+    }
+
+    /// Performs the `instantiate` giving the contract the address predicted for the `salt`
+    #[doc(hidden)]
+    pub fn instantiate_salted<T>(
+        &self,
+        code_id: u64,
+        creator: &str,
+        salt: &[u8],
+        instantiate: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let addr = self.predict_address(code_id, creator, salt)?;
+        if self
+            .app
+            .borrow()
+            .wrap()
+            .query_wasm_contract_info(&addr)
+            .is_ok()
+        {
+            anyhow::bail!("Contract with the address {addr} already exists");
+        }
+
+        self.app
+            .borrow_mut()
+            .init_modules(|_, _, storage| storage.set(SALTED_ADDRESS_KEY, addr.as_bytes()));
+        let result = instantiate();
+        // The address is left unused if the instantiation failed or the generator is missing
+        let unused = self.app.borrow_mut().init_modules(|_, _, storage| {
+            let unused = storage.get(SALTED_ADDRESS_KEY).is_some();
+            storage.remove(SALTED_ADDRESS_KEY);
+            unused
+        });
+        assert!(
+            !unused || result.is_err(),
+            "Salted addresses require the `SaltedAddressGenerator` in the `WasmKeeper`, \
+             like in the `App::bech32`"
+        );
+        result
+    }
+}
+
+/// Storage key of the address the next instantiated contract gets
+const SALTED_ADDRESS_KEY: &[u8] = b"sylvia_salted_address";
+
+/// Checksum of the code stored in the multitest, used to predict the salted addresses.
+///
+/// The `WasmKeeper` does not answer `WasmQuery::CodeInfo`, so the contracts predicting the
+/// addresses have to get the checksum from the test.
+pub fn code_checksum(code_id: u64) -> Vec<u8> {
+    Sha256::digest(code_id.to_be_bytes()).to_vec()
+}
+
+/// `AddressGenerator` giving the contracts instantiated with a salt their instantiate2 address.
+///
+/// Other contracts get the `contract{N}` addresses, like with the default generator of the
+/// `WasmKeeper`.
+pub struct SaltedAddressGenerator;
+
+impl AddressGenerator for SaltedAddressGenerator {
+    fn next_address(&self, storage: &mut dyn Storage) -> Addr {
+        if let Some(addr) = storage.get(SALTED_ADDRESS_KEY) {
+            storage.remove(SALTED_ADDRESS_KEY);
+            return Addr::unchecked(String::from_utf8_lossy(&addr));
+        }
+
+        let (prefix, end) = wasm_storage::range(wasm_storage::contracts_prefix());
+        let count = storage
+            .range_keys(Some(&prefix), Some(&end), Order::Ascending)
+            .count();
+        Addr::unchecked(format!("contract{count}"))
+    }
+}
@@ -0,0 +1,433 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+
+use cosmwasm_std::{Addr, Api, Binary, BlockInfo, CustomQuery, HexBinary, Order, Record, Storage};
+use cw_multi_test::{Bank, Distribution, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::{App, DumpStorage};
+
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    App<cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>>
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Captures the state of the chain, so it can be rolled back with `App::restore`.
+    ///
+    /// The snapshot covers the whole storage, so the contracts state and metadata, as well as
+    /// the bank and staking balances, and the block info. Stored codes are not captured - codes
+    /// stored after the snapshot was taken remain available after restoring it.
+    ///
+    /// The state kept by the `App` itself is not captured either, as it records what the test
+    /// exercised rather than the chain state - calls done after the snapshot was taken stay
+    /// counted in the `App::coverage` and listed in `MockContract::calls`. The settings like
+    /// invariants or `App::auto_advance_blocks` are not changed by restoring the snapshot.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            storage: self.dump_storage(),
+            block: self.block_info(),
+        }
+    }
+
+    /// Rolls the chain back to the state captured by `App::snapshot`
+    pub fn restore(&self, snapshot: &Snapshot) {
+        self.restore_storage(&snapshot.storage);
+        self.set_block(snapshot.block.clone());
+    }
+
+    /// Enables or disables recording storage changes of every call through the proxies, returned
+    /// as the `CallResponse::storage_diff`.
+    ///
+    /// The whole storage of the app, including all the contracts and modules, is copied before
+    /// and after every call and then compared, so each call costs two full storage dumps.
+    pub fn record_storage_diffs(&self, record: bool) {
+        let dump_storage: DumpStorage<_> = |app: &cw_multi_test::App<_, _, _, _, _, _, _, _, _>| {
+            app.read_module(|_, _, storage| storage.range(None, None, Order::Ascending).collect())
+        };
+        self.dump_storage.set(record.then_some(dump_storage));
+    }
+
+    /// Raw storage of the `contract`, to be saved as JSON with `ContractState::to_json`
+    #[track_caller]
+    pub fn export_state(&self, contract: &Addr) -> ContractState {
+        self.assert_contract_exists(contract);
+        let (prefix, end) = wasm_storage::range(wasm_storage::contract_data_prefix(contract));
+        let models = self.app.borrow().read_module(|_, _, storage| {
+            storage
+                .range(Some(&prefix), Some(&end), Order::Ascending)
+                .map(|(key, value)| StateModel {
+                    key: key[prefix.len()..].to_vec().into(),
+                    value: value.into(),
+                })
+                .collect()
+        });
+        ContractState { models }
+    }
+
+    /// Replaces the whole raw storage of the `contract` with the `state`.
+    ///
+    /// The contract has to be instantiated already, and only its storage is replaced - the
+    /// code, admin and the balances are not changed.
+    #[track_caller]
+    pub fn import_state(&self, contract: &Addr, state: &ContractState) {
+        self.assert_contract_exists(contract);
+        let (prefix, end) = wasm_storage::range(wasm_storage::contract_data_prefix(contract));
+
+        self.app.borrow_mut().init_modules(|_, _, storage| {
+            let keys: Vec<_> = storage
+                .range_keys(Some(&prefix), Some(&end), Order::Ascending)
+                .collect();
+            keys.iter().for_each(|key| storage.remove(key));
+            state.models.iter().for_each(|model| {
+                storage.set(
+                    &[prefix.as_slice(), model.key.as_slice()].concat(),
+                    &model.value,
+                )
+            });
+        })
+    }
+
+    #[track_caller]
+    fn assert_contract_exists(&self, contract: &Addr) {
+        let key = wasm_storage::contract_key(contract);
+        if self
+            .app
+            .borrow()
+            .read_module(|_, _, storage| storage.get(&key))
+            .is_none()
+        {
+            panic!("Contract {contract} does not exist");
+        }
+    }
+
+    /// Raw content of the whole chain storage
+    pub(crate) fn dump_storage(&self) -> Vec<Record> {
+        self.app
+            .borrow()
+            .read_module(|_, _, storage| storage.range(None, None, Order::Ascending).collect())
+    }
+
+    /// Replaces the whole chain storage with the dumped one
+    pub(crate) fn restore_storage(&self, records: &[Record]) {
+        self.app.borrow_mut().init_modules(|_, _, storage| {
+            let keys: Vec<_> = storage.range_keys(None, None, Order::Ascending).collect();
+            keys.iter().for_each(|key| storage.remove(key));
+            records
+                .iter()
+                .for_each(|(key, value)| storage.set(key, value));
+        })
+    }
+}
+
+/// State of the `App` captured with `App::snapshot`
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    storage: Vec<Record>,
+    block: BlockInfo,
+}
+
+/// Raw storage of a single contract.
+///
+/// Serializes the same way as the contract state queried from the chain, with hex keys and
+/// base64 values, so the state exported from a node can be imported into the multitest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractState {
+    pub models: Vec<StateModel>,
+}
+
+/// Single entry of the `ContractState`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateModel {
+    pub key: HexBinary,
+    pub value: Binary,
+}
+
+impl ContractState {
+    /// Parses the state exported from the chain or saved with `ContractState::to_json`.
+    ///
+    /// Other fields of the chain response, like the `pagination`, are ignored.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Serializing contract state cannot fail")
+    }
+}
+
+/// Raw storage layout of the `WasmKeeper` of cw-multi-test 0.16.5.
+///
+/// The keeper doesn't expose its storage, so the layout is pinned here and has to be checked on
+/// every cw-multi-test upgrade - the `tests/state.rs` fails loudly if it changes.
+pub(super) mod wasm_storage {
+    use cosmwasm_std::Addr;
+
+    /// Namespace of the `WasmKeeper` in the chain storage
+    const NAMESPACE: &[u8] = b"wasm";
+    /// Map of the contracts metadata, keyed by the address
+    const CONTRACTS: &[u8] = b"contracts";
+    /// Prefix of the contract data namespace, followed by the address
+    const CONTRACT_DATA: &[u8] = b"contract_data/";
+
+    /// Length prefixed namespaces, as encoded by `cosmwasm_storage` and `cw_storage_plus`
+    fn length_prefixed(namespaces: &[&[u8]]) -> Vec<u8> {
+        namespaces
+            .iter()
+            .flat_map(|namespace| {
+                (namespace.len() as u16)
+                    .to_be_bytes()
+                    .into_iter()
+                    .chain(namespace.iter().copied())
+            })
+            .collect()
+    }
+
+    /// Key range of all the keys starting with the `prefix`, as the prefix and the end
+    pub fn range(prefix: Vec<u8>) -> (Vec<u8>, Vec<u8>) {
+        let mut end = prefix.clone();
+        *end.last_mut().unwrap() += 1;
+        (prefix, end)
+    }
+
+    /// Prefix of the contracts metadata keys
+    pub fn contracts_prefix() -> Vec<u8> {
+        length_prefixed(&[NAMESPACE, CONTRACTS])
+    }
+
+    /// Key of the `contract` metadata
+    pub fn contract_key(contract: &Addr) -> Vec<u8> {
+        [contracts_prefix().as_slice(), contract.as_bytes()].concat()
+    }
+
+    /// Prefix of the `contract` data
+    pub fn contract_data_prefix(contract: &Addr) -> Vec<u8> {
+        let namespace = [CONTRACT_DATA, contract.as_bytes()].concat();
+        length_prefixed(&[NAMESPACE, &namespace])
+    }
+
+    /// Splits the raw key into the contract and the key in its data, if it is a contract data
+    pub fn split_contract_data(key: &[u8]) -> Option<(Addr, &[u8])> {
+        let (namespace, rest) = super::split_namespace(key)?;
+        let (namespace, key) = (namespace == NAMESPACE)
+            .then(|| super::split_namespace(rest))
+            .flatten()?;
+        let addr = namespace.strip_prefix(CONTRACT_DATA)?;
+        Some((Addr::unchecked(String::from_utf8_lossy(addr)), key))
+    }
+}
+
+/// Raw storage changes done by a single call
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageDiff {
+    /// Changes of the contracts storages, with keys as seen by the contracts
+    pub contracts: BTreeMap<Addr, Vec<KeyChange>>,
+    /// Changes of the chain modules like `bank` or `wasm` metadata, by module namespace
+    pub modules: BTreeMap<String, Vec<KeyChange>>,
+}
+
+/// Change of a single storage key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyChange {
+    pub key: Vec<u8>,
+    /// Value before the call, `None` if the key was added
+    pub old: Option<Vec<u8>>,
+    /// Value after the call, `None` if the key was removed
+    pub new: Option<Vec<u8>>,
+}
+
+impl KeyChange {
+    /// Change of the storage size in bytes, counting both the key and the value
+    pub fn growth(&self) -> i64 {
+        let size = |value: &Option<Vec<u8>>| {
+            value
+                .as_ref()
+                .map_or(0, |value| (self.key.len() + value.len()) as i64)
+        };
+        size(&self.new) - size(&self.old)
+    }
+}
+
+impl StorageDiff {
+    pub(super) fn new(before: &[Record], after: &[Record]) -> Self {
+        let mut diff = Self::default();
+        let mut before = before.iter().peekable();
+        let mut after = after.iter().peekable();
+
+        // Both dumps are sorted by the key
+        loop {
+            let (key, old, new) = match (before.peek(), after.peek()) {
+                (None, None) => break,
+                (Some((old_key, _)), Some((new_key, _))) if old_key == new_key => {
+                    let (key, old) = before.next().unwrap();
+                    let (_, new) = after.next().unwrap();
+                    if old == new {
+                        continue;
+                    }
+                    (key, Some(old), Some(new))
+                }
+                (Some((old_key, _)), Some((new_key, _))) if old_key > new_key => {
+                    let (key, new) = after.next().unwrap();
+                    (key, None, Some(new))
+                }
+                (None, Some(_)) => {
+                    let (key, new) = after.next().unwrap();
+                    (key, None, Some(new))
+                }
+                (Some(_), _) => {
+                    let (key, old) = before.next().unwrap();
+                    (key, Some(old), None)
+                }
+            };
+            diff.push(key, old.cloned(), new.cloned());
+        }
+
+        diff
+    }
+
+    fn push(&mut self, key: &[u8], old: Option<Vec<u8>>, new: Option<Vec<u8>>) {
+        let (namespace, rest) = split_namespace(key).unwrap_or((b"", key));
+
+        match wasm_storage::split_contract_data(key) {
+            Some((addr, key)) => self.contracts.entry(addr).or_default().push(KeyChange {
+                key: key.to_vec(),
+                old,
+                new,
+            }),
+            None => self
+                .modules
+                .entry(String::from_utf8_lossy(namespace).into_owned())
+                .or_default()
+                .push(KeyChange {
+                    key: rest.to_vec(),
+                    old,
+                    new,
+                }),
+        }
+    }
+
+    /// Changes of the contract storage
+    pub fn contract(&self, addr: &Addr) -> &[KeyChange] {
+        self.contracts.get(addr).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty() && self.modules.is_empty()
+    }
+
+    /// Change of the whole storage size in bytes
+    pub fn growth(&self) -> i64 {
+        self.changes().map(KeyChange::growth).sum()
+    }
+
+    /// Bytes of the keys and values written by the call
+    pub fn written_bytes(&self) -> usize {
+        self.changes()
+            .filter_map(|change| Some(change.key.len() + change.new.as_ref()?.len()))
+            .sum()
+    }
+
+    fn changes(&self) -> impl Iterator<Item = &KeyChange> {
+        self.contracts
+            .values()
+            .chain(self.modules.values())
+            .flatten()
+    }
+}
+
+/// Prints the changes with the keys decoded as `cw_storage_plus` items and maps where possible
+impl Display for StorageDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let groups = self
+            .contracts
+            .iter()
+            .map(|(addr, changes)| (format!("contract {addr}"), changes))
+            .chain(
+                self.modules
+                    .iter()
+                    .map(|(module, changes)| (format!("module {module}"), changes)),
+            );
+
+        for (name, changes) in groups {
+            writeln!(f, "{name}:")?;
+            for change in changes {
+                let key = decode_key(&change.key);
+                match (&change.old, &change.new) {
+                    (None, Some(new)) => writeln!(f, "  + {key} ({} bytes)", new.len())?,
+                    (Some(old), None) => writeln!(f, "  - {key} ({} bytes)", old.len())?,
+                    (Some(old), Some(new)) => {
+                        writeln!(f, "  ~ {key} ({} -> {} bytes)", old.len(), new.len())?
+                    }
+                    (None, None) => (),
+                }
+            }
+        }
+
+        write!(
+            f,
+            "{} bytes written, storage grown by {} bytes",
+            self.written_bytes(),
+            self.growth()
+        )
+    }
+}
+
+/// Splits the length prefixed namespace off the key
+fn split_namespace(key: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (len, rest) = (key.get(..2)?, &key[2..]);
+    let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+    (len <= rest.len()).then(|| rest.split_at(len))
+}
+
+/// Decodes the `cw_storage_plus` key - an item name, or a map name followed by its key parts
+fn decode_key(key: &[u8]) -> String {
+    let readable = |part: &[u8]| {
+        std::str::from_utf8(part)
+            .ok()
+            .filter(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_graphic()))
+            .map(str::to_owned)
+    };
+
+    if let Some(name) = readable(key) {
+        return name;
+    }
+
+    let Some((name, mut rest)) = split_namespace(key) else {
+        return to_hex(key);
+    };
+    let Some(name) = readable(name) else {
+        return to_hex(key);
+    };
+
+    // All the key parts but the last one are length prefixed
+    let mut parts = vec![];
+    while let Some((part, remaining)) =
+        split_namespace(rest).filter(|(part, rest)| !part.is_empty() && !rest.is_empty())
+    {
+        parts.push(part);
+        rest = remaining;
+    }
+    parts.push(rest);
+
+    let parts: Vec<_> = parts
+        .into_iter()
+        .map(|part| readable(part).map_or_else(|| to_hex(part), |part| format!("{part:?}")))
+        .collect();
+    format!("{name}[{}]", parts.join(", "))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{hex}")
+}
@@ -0,0 +1,129 @@
+use std::fmt::Debug;
+
+use cosmwasm_std::{
+    Addr, Api, Binary, CosmosMsg, CustomQuery, Empty, Reply, ReplyOn, Storage, SubMsg,
+    SubMsgResponse, SubMsgResult, WasmMsg,
+};
+use cw_multi_test::{AppResponse, Bank, Distribution, Gov, Ibc, Module, Staking, Wasm};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::App;
+
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>
+    App<cw_multi_test::App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT>>
+where
+    CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Calls the `reply` entry point of the `contract` directly with the crafted `reply`.
+    ///
+    /// The reply is handled the same way as the one on a submessage - the messages it returns
+    /// are executed, and all its state changes are reverted if it fails. Works only for the
+    /// contracts stored with the generated `CodeId::store_code`, any other contract is not
+    /// called at all.
+    pub fn reply(&self, contract: &Addr, reply: Reply) -> anyhow::Result<AppResponse> {
+        let code_id = self
+            .app
+            .borrow()
+            .wrap()
+            .query_wasm_contract_info(contract)?
+            .code_id;
+        anyhow::ensure!(
+            self.tracked_codes.borrow().contains(&code_id),
+            "Reply cannot be injected into {contract}, it has to be stored with the generated \
+             `CodeId::store_code`"
+        );
+
+        // Multitest calls the entry points only with messages, so the reply is passed aside and
+        // the tracked contract calls its `reply` instead of `sudo` with it
+        *self.injected_reply.borrow_mut() = Some((contract.clone(), reply));
+        let result = self.app.borrow_mut().wasm_sudo(contract.clone(), &Empty {});
+        self.injected_reply.take();
+        result
+    }
+}
+
+/// Submessage returned by a contract
+#[derive(Clone, Debug, PartialEq)]
+pub struct DispatchedSubMsg {
+    /// Contract which returned the submessage
+    pub sender: Addr,
+    pub id: u64,
+    pub reply_on: ReplyOn,
+    pub gas_limit: Option<u64>,
+    /// The message, with the custom message kept as JSON
+    pub msg: CosmosMsg<serde_json::Value>,
+}
+
+impl DispatchedSubMsg {
+    pub(super) fn new<ExecC: Serialize>(sender: &Addr, submsg: &SubMsg<ExecC>) -> Self {
+        let msg = serde_json::to_value(&submsg.msg)
+            .and_then(serde_json::from_value)
+            .expect("Cosmos message not serializable");
+        Self {
+            sender: sender.clone(),
+            id: submsg.id,
+            reply_on: submsg.reply_on.clone(),
+            gas_limit: submsg.gas_limit,
+            msg,
+        }
+    }
+
+    /// Address of the contract the message is sent to, if it is a wasm message
+    pub fn contract_addr(&self) -> Option<&str> {
+        match &self.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+            | CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }) => Some(contract_addr),
+            _ => None,
+        }
+    }
+
+    /// Message executed on the contract, decoded as `M` - like the `ExecMsg` generated for
+    /// the sylvia contract.
+    ///
+    /// Returns `None` if the message is not a wasm execution or it is not a `M`.
+    pub fn exec_msg<M: DeserializeOwned>(&self) -> Option<M> {
+        match &self.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => cosmwasm_std::from_binary(msg).ok(),
+            _ => None,
+        }
+    }
+
+    /// Custom message decoded as `C`
+    pub fn custom_msg<C: DeserializeOwned>(&self) -> Option<C> {
+        match &self.msg {
+            CosmosMsg::Custom(msg) => serde_json::from_value(msg.clone()).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// `Reply` on the successful submessage with the `id`, returning the `data`
+pub fn reply_ok(id: u64, data: Option<Binary>) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data,
+        }),
+    }
+}
+
+/// `Reply` on the submessage with the `id` failed with the `error`
+pub fn reply_err(id: u64, error: impl Into<String>) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Err(error.into()),
+    }
+}
@@ -0,0 +1,218 @@
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use anyhow::Context;
+use cosmwasm_std::{
+    Addr, Api, Binary, CustomQuery, Deps, DepsMut, Env, MessageInfo, Reply, Response, Storage,
+};
+use cw_multi_test::{Bank, Contract, Distribution, Gov, Ibc, Module, Staking, WasmKeeper};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::call::ContractFailed;
+use super::{msg_variant, App, CallRoot, Coverage, DispatchedSubMsg};
+
+impl<BankT, ApiT, StorageT, ExecC, QueryC, CustomT, StakingT, DistrT, IbcT, GovT>
+    App<
+        cw_multi_test::App<
+            BankT,
+            ApiT,
+            StorageT,
+            CustomT,
+            WasmKeeper<ExecC, QueryC>,
+            StakingT,
+            DistrT,
+            IbcT,
+            GovT,
+        >,
+    >
+where
+    ExecC: Debug + PartialEq + Clone + JsonSchema + Serialize + DeserializeOwned + 'static,
+    QueryC: CustomQuery + DeserializeOwned + 'static,
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module<ExecT = ExecC, QueryT = QueryC>,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+{
+    /// Stores the code wrapped to record the messages dispatched to it in the coverage, and
+    /// to accept the replies injected with `App::reply`
+    #[doc(hidden)]
+    pub fn store_tracked_code(
+        &self,
+        contract: &str,
+        exec: &[&str],
+        query: &[&str],
+        migrate: bool,
+        code: Box<dyn Contract<ExecC, QueryC>>,
+    ) -> u64 {
+        let messages = std::iter::once("instantiate".to_owned())
+            .chain(exec.iter().filter_map(|msg| variant_name("exec", msg)))
+            .chain(query.iter().filter_map(|msg| variant_name("query", msg)))
+            .chain(migrate.then(|| "migrate".to_owned()));
+
+        self.coverage
+            .borrow_mut()
+            .contracts
+            .entry(contract.to_owned())
+            .or_default()
+            .register(messages);
+
+        let code_id = self.app.borrow_mut().store_code(Box::new(TrackedContract {
+            contract: contract.to_owned(),
+            code,
+            coverage: self.coverage.clone(),
+            submsgs: self.submsgs.clone(),
+            injected_reply: self.injected_reply.clone(),
+            call_root: self.call_root.clone(),
+        }));
+        self.tracked_codes.borrow_mut().insert(code_id);
+        code_id
+    }
+}
+
+/// Contract recording the dispatched messages and marking the errors it returns
+struct TrackedContract<ExecC, QueryC> {
+    contract: String,
+    code: Box<dyn Contract<ExecC, QueryC>>,
+    coverage: Rc<RefCell<Coverage>>,
+    submsgs: Rc<RefCell<Option<Vec<DispatchedSubMsg>>>>,
+    injected_reply: Rc<RefCell<Option<(Addr, Reply)>>>,
+    call_root: CallRoot,
+}
+
+impl<ExecC, QueryC> TrackedContract<ExecC, QueryC> {
+    fn record_submsgs(&self, contract: &Addr, result: &anyhow::Result<Response<ExecC>>)
+    where
+        ExecC: Serialize,
+    {
+        let mut submsgs = self.submsgs.borrow_mut();
+        let (Ok(response), Some(submsgs)) = (result, &mut *submsgs) else {
+            return;
+        };
+        submsgs.extend(
+            response
+                .messages
+                .iter()
+                .map(|submsg| DispatchedSubMsg::new(contract, submsg)),
+        );
+    }
+
+    fn record<T>(&self, message: Option<String>, result: &anyhow::Result<T>) {
+        let Some(message) = message else {
+            return;
+        };
+        self.coverage
+            .borrow_mut()
+            .contracts
+            .entry(self.contract.clone())
+            .or_default()
+            .record(message, result.is_ok());
+    }
+
+    /// Name of the dispatched enum message variant
+    fn variant(kind: &str, msg: &[u8]) -> Option<String> {
+        variant_name(kind, &msg_variant(msg)?)
+    }
+}
+
+/// Name of the variant in the coverage, `None` for the reserved sylvia messages
+fn variant_name(kind: &str, variant: &str) -> Option<String> {
+    (!crate::types::SylviaQueryMsg::messages().contains(&variant))
+        .then(|| format!("{kind} {variant}"))
+}
+
+impl<ExecC, QueryC> Contract<ExecC, QueryC> for TrackedContract<ExecC, QueryC>
+where
+    ExecC: Clone + Debug + PartialEq + JsonSchema + Serialize,
+    QueryC: CustomQuery,
+{
+    fn execute(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        let message = Self::variant("exec", &msg);
+        let failed = ContractFailed::enter(&self.call_root, &env.contract.address);
+        let result = self.code.execute(deps, env, info, msg);
+        self.record(message, &result);
+        self.record_submsgs(&failed.contract, &result);
+        result.context(failed)
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        let failed = ContractFailed::enter(&self.call_root, &env.contract.address);
+        let result = self.code.instantiate(deps, env, info, msg);
+        self.record(Some("instantiate".to_owned()), &result);
+        self.record_submsgs(&failed.contract, &result);
+        result.context(failed)
+    }
+
+    fn query(&self, deps: Deps<QueryC>, env: Env, msg: Vec<u8>) -> anyhow::Result<Binary> {
+        let message = Self::variant("query", &msg);
+        let result = self.code.query(deps, env, msg);
+        self.record(message, &result);
+        result
+    }
+
+    fn sudo(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        let injected_reply = {
+            let mut injected_reply = self.injected_reply.borrow_mut();
+            match &*injected_reply {
+                Some((contract, _)) if *contract == env.contract.address => injected_reply.take(),
+                _ => None,
+            }
+        };
+        if let Some((_, reply)) = injected_reply {
+            return self.reply(deps, env, reply);
+        }
+
+        let failed = ContractFailed::enter(&self.call_root, &env.contract.address);
+        let result = self.code.sudo(deps, env, msg);
+        self.record_submsgs(&failed.contract, &result);
+        result.context(failed)
+    }
+
+    fn reply(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        msg: Reply,
+    ) -> anyhow::Result<Response<ExecC>> {
+        let failed = ContractFailed::enter(&self.call_root, &env.contract.address);
+        let result = self.code.reply(deps, env, msg);
+        self.record_submsgs(&failed.contract, &result);
+        result.context(failed)
+    }
+
+    fn migrate(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        msg: Vec<u8>,
+    ) -> anyhow::Result<Response<ExecC>> {
+        let failed = ContractFailed::enter(&self.call_root, &env.contract.address);
+        let result = self.code.migrate(deps, env, msg);
+        self.record(Some("migrate".to_owned()), &result);
+        self.record_submsgs(&failed.contract, &result);
+        result.context(failed)
+    }
+}
@@ -0,0 +1,195 @@
+//! Schema of a single interface.
+//!
+//! Unlike the contract schema generated with `cosmwasm_schema::write_api`, the interface schema
+//! describes only messages of the interface, so it can be published independently of any
+//! contract implementing it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use ::serde_json::Value;
+use cosmwasm_schema::QueryResponses;
+use schemars::gen::SchemaSettings;
+use schemars::schema::RootSchema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::types::Interface;
+
+/// Number of bytes of the schema digest kept in the schema
+const SCHEMA_DIGEST_LEN: usize = 8;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InterfaceSchema {
+    pub interface_name: String,
+    pub interface_id: String,
+    pub schema_digest: String,
+    pub exec: RootSchema,
+    pub query: RootSchema,
+    pub responses: BTreeMap<String, RootSchema>,
+}
+
+impl InterfaceSchema {
+    pub fn new<I, Exec, Query>() -> Self
+    where
+        I: Interface,
+        Exec: JsonSchema,
+        Query: JsonSchema + QueryResponses,
+    {
+        let exec = SchemaSettings::draft07()
+            .into_generator()
+            .into_root_schema_for::<Exec>();
+        let query = SchemaSettings::draft07()
+            .into_generator()
+            .into_root_schema_for::<Query>();
+        let responses = Query::response_schemas()
+            .unwrap_or_else(|err| panic!("Invalid responses of {} interface: {}", I::NAME, err));
+
+        let schema_digest = schema_digest(&exec, &query, &responses);
+
+        Self {
+            interface_name: I::NAME.to_owned(),
+            interface_id: I::ID.to_owned(),
+            schema_digest,
+            exec,
+            query,
+            responses,
+        }
+    }
+
+    /// Renders schema as a pretty printed JSON
+    pub fn render(&self) -> String {
+        ::serde_json::to_string_pretty(self).expect("Interface schema is always serializable")
+    }
+
+    /// Writes schema to `{dir}/{interface_name}.json`, creating `dir` if needed
+    pub fn export(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.json", self.interface_name.to_lowercase()));
+        std::fs::write(path, self.render())
+    }
+
+    /// Returns messages of this interface missing in the contract schema.
+    ///
+    /// `exec` and `query` are root schemas of the contract messages - typically the
+    /// `ContractExecMsg` and `ContractQueryMsg` generated by `#[contract]`. Missing messages
+    /// are prefixed with `exec.` or `query.`. An empty result means the contract exposes
+    /// the whole interface.
+    pub fn missing_messages(&self, exec: &RootSchema, query: &RootSchema) -> Vec<String> {
+        let contract_exec = message_names(exec);
+        let contract_query = message_names(query);
+
+        let missing_exec = message_names(&self.exec)
+            .into_iter()
+            .filter(|msg| !contract_exec.contains(msg))
+            .map(|msg| format!("exec.{}", msg));
+        let missing_query = message_names(&self.query)
+            .into_iter()
+            .filter(|msg| !contract_query.contains(msg))
+            .map(|msg| format!("query.{}", msg));
+
+        missing_exec.chain(missing_query).collect()
+    }
+}
+
+/// Digest of the messages and responses JSON schema. Unlike the interface id it covers the
+/// definitions of all the used types, so it is an off chain check on top of the id. Titles of
+/// the messages enums contain the trait name and descriptions come from the doc comments, so
+/// both are skipped.
+fn schema_digest(
+    exec: &RootSchema,
+    query: &RootSchema,
+    responses: &BTreeMap<String, RootSchema>,
+) -> String {
+    let mut schema = ::serde_json::json!({
+        "exec": exec,
+        "query": query,
+        "responses": responses,
+    });
+    for msgs in ["exec", "query"] {
+        if let Some(msgs) = schema[msgs].as_object_mut() {
+            msgs.remove("title");
+        }
+    }
+    strip_descriptions(&mut schema);
+
+    let schema = ::serde_json::to_vec(&schema).expect("Schema is always serializable");
+    let digest = Sha256::digest(schema);
+    digest[..SCHEMA_DIGEST_LEN]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Removes `description` keywords. Only string values are removed, as a schema object under
+/// the `description` key is a property named so.
+fn strip_descriptions(schema: &mut Value) {
+    match schema {
+        Value::Object(map) => {
+            if matches!(map.get("description"), Some(Value::String(_))) {
+                map.remove("description");
+            }
+            map.values_mut().for_each(strip_descriptions);
+        }
+        Value::Array(items) => items.iter_mut().for_each(strip_descriptions),
+        _ => (),
+    }
+}
+
+/// Collects names of all messages reachable from the root schema.
+fn message_names(root: &RootSchema) -> BTreeSet<String> {
+    messages(root).into_keys().collect()
+}
+
+/// Collects all messages reachable from the root schema along with their payload schemas.
+///
+/// Messages are externally tagged enum variants, so they are represented as objects with
+/// a single required property. Untagged glue enums are resolved through `anyOf`/`oneOf`
+/// and `$ref`s to the definitions.
+pub(crate) fn messages(root: &RootSchema) -> BTreeMap<String, Value> {
+    let root = ::serde_json::to_value(root).expect("Schema is always serializable");
+    let definitions = root.get("definitions").cloned().unwrap_or(Value::Null);
+
+    let mut messages = BTreeMap::new();
+    let mut visited = BTreeSet::new();
+    collect_messages(&root, &definitions, &mut visited, &mut messages);
+    messages
+}
+
+fn collect_messages(
+    schema: &Value,
+    definitions: &Value,
+    visited: &mut BTreeSet<String>,
+    messages: &mut BTreeMap<String, Value>,
+) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        if visited.insert(name.to_owned()) {
+            if let Some(def) = definitions.get(name) {
+                collect_messages(def, definitions, visited, messages);
+            }
+        }
+        return;
+    }
+
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            variants
+                .iter()
+                .for_each(|variant| collect_messages(variant, definitions, visited, messages));
+        }
+    }
+
+    let required = schema.get("required").and_then(Value::as_array);
+    let properties = schema.get("properties").and_then(Value::as_object);
+    if let (Some([name]), Some(properties)) = (required.map(Vec::as_slice), properties) {
+        if let Some((name, payload)) = name
+            .as_str()
+            .and_then(|name| properties.get_key_value(name))
+        {
+            messages.insert(name.to_owned(), payload.clone());
+        }
+    }
+}
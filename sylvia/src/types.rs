@@ -1,4 +1,9 @@
-use cosmwasm_std::{CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo, QuerierWrapper,
+    StdResult,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 pub struct ReplyCtx<'a, C: CustomQuery = Empty> {
     pub deps: DepsMut<'a, C>,
@@ -76,6 +81,12 @@ impl<'a, C: CustomQuery> From<(DepsMut<'a, C>, Env)> for ReplyCtx<'a, C> {
     }
 }
 
+impl<'a, C: CustomQuery> From<(DepsMut<'a, C>, Env)> for SudoCtx<'a, C> {
+    fn from((deps, env): (DepsMut<'a, C>, Env)) -> Self {
+        Self { deps, env }
+    }
+}
+
 impl<'a, C: CustomQuery> From<(DepsMut<'a, C>, Env, MessageInfo)> for ExecCtx<'a, C> {
     fn from((deps, env, info): (DepsMut<'a, C>, Env, MessageInfo)) -> Self {
         Self { deps, env, info }
@@ -93,3 +104,91 @@ impl<'a, C: CustomQuery> From<(Deps<'a, C>, Env)> for QueryCtx<'a, C> {
         Self { deps, env }
     }
 }
+
+/// Static description of the interface generated by the `#[interface]` macro.
+///
+/// `ID` is derived at compile time from the names, fields and query responses of all the
+/// interface messages, so it changes whenever any of them changes. Types are compared by their
+/// names - to verify the types definitions as well, compare `InterfaceSchema::schema_digest`
+/// off chain.
+pub trait Interface {
+    const NAME: &'static str;
+    const ID: &'static str;
+    const EXEC_MESSAGES: &'static [&'static str];
+    const QUERY_MESSAGES: &'static [&'static str];
+}
+
+/// Description of the interface implemented by the contract, as returned by the `sylvia_info` query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct InterfaceInfo {
+    pub name: String,
+    pub id: String,
+    pub exec: Vec<String>,
+    pub query: Vec<String>,
+}
+
+impl InterfaceInfo {
+    pub fn new<I: Interface>() -> Self {
+        let to_owned = |msgs: &[&str]| msgs.iter().map(|msg| msg.to_string()).collect();
+
+        Self {
+            name: I::NAME.to_owned(),
+            id: I::ID.to_owned(),
+            exec: to_owned(I::EXEC_MESSAGES),
+            query: to_owned(I::QUERY_MESSAGES),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SylviaInfoResponse {
+    pub contract: String,
+    pub version: String,
+    pub interfaces: Vec<InterfaceInfo>,
+}
+
+impl SylviaInfoResponse {
+    /// Checks if the contract implements exactly this version of the interface - one with the
+    /// same name and id
+    pub fn supports<I: Interface>(&self) -> bool {
+        self.interfaces
+            .iter()
+            .any(|interface| interface.name == I::NAME && interface.id == I::ID)
+    }
+}
+
+/// Query reserved by sylvia. It is part of every contract `ContractQueryMsg`, so any contract
+/// can be asked which interfaces it implements.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    not(target_arch = "wasm32"),
+    derive(JsonSchema, cosmwasm_schema::QueryResponses)
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SylviaQueryMsg {
+    #[cfg_attr(not(target_arch = "wasm32"), returns(SylviaInfoResponse))]
+    SylviaInfo {},
+}
+
+impl SylviaQueryMsg {
+    pub const fn messages() -> [&'static str; 1] {
+        ["sylvia_info"]
+    }
+
+    pub fn is_message(name: &str) -> bool {
+        name == "sylvia_info"
+    }
+
+    pub fn dispatch(self, info: SylviaInfoResponse) -> StdResult<Binary> {
+        match self {
+            Self::SylviaInfo {} => to_binary(&info),
+        }
+    }
+
+    pub fn query<C: CustomQuery>(
+        querier: &QuerierWrapper<C>,
+        contract: &Addr,
+    ) -> StdResult<SylviaInfoResponse> {
+        querier.query_wasm_smart(contract, &Self::SylviaInfo {})
+    }
+}
@@ -0,0 +1,420 @@
+//! TypeScript types and clients generation.
+//!
+//! Generated file contains types for all the messages and query responses, a query client
+//! with method per query message and a message composer creating `MsgExecuteContract` for
+//! every exec message. Untagged glue messages are flattened, so the contract clients contain
+//! methods for the interfaces messages as well.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use ::serde_json::Value;
+use cosmwasm_schema::Api;
+use schemars::schema::RootSchema;
+
+use crate::schema::{messages, InterfaceSchema};
+
+pub struct TsCodegen {
+    name: String,
+    instantiate: Option<RootSchema>,
+    exec: Option<RootSchema>,
+    query: Option<RootSchema>,
+    responses: BTreeMap<String, RootSchema>,
+}
+
+impl TsCodegen {
+    /// Creates generator for the contract API, as returned by `cosmwasm_schema::generate_api!`
+    pub fn contract(api: &Api) -> Self {
+        Self {
+            name: to_pascal_case(&api.contract_name),
+            instantiate: Some(api.instantiate.clone()),
+            exec: api.execute.clone(),
+            query: api.query.clone(),
+            responses: api.responses.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Creates generator for the interface, so clients can be used with any contract
+    /// implementing it
+    pub fn interface(schema: &InterfaceSchema) -> Self {
+        Self {
+            name: to_pascal_case(&schema.interface_name),
+            instantiate: None,
+            exec: Some(schema.exec.clone()),
+            query: Some(schema.query.clone()),
+            responses: schema.responses.clone(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::from(
+            "// This file was generated by sylvia. Do not edit it manually, regenerate it instead.\n",
+        );
+
+        let mut definitions = BTreeMap::new();
+        let roots = [&self.instantiate, &self.exec, &self.query]
+            .into_iter()
+            .flatten()
+            .chain(self.responses.values());
+        for root in roots {
+            let (name, schema, defs) = split_root(root);
+            definitions.extend(defs);
+            definitions.insert(name, schema);
+        }
+
+        for (name, schema) in &definitions {
+            out.push('\n');
+            if let Some(description) = schema.get("description").and_then(Value::as_str) {
+                out.push_str("/**\n");
+                for line in description.replace("*/", "*\\/").lines() {
+                    writeln!(out, "{}", format!(" * {}", line).trim_end()).unwrap();
+                }
+                out.push_str(" */\n");
+            }
+            writeln!(out, "export type {} = {};", name, ts_type(schema)).unwrap();
+        }
+
+        out.push_str(PRELUDE);
+        self.render_query_client(&mut out);
+        self.render_message_composer(&mut out);
+        out
+    }
+
+    /// Writes generated code to `{dir}/{Name}.ts`, creating `dir` if needed
+    pub fn export(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(format!("{}.ts", self.name)), self.render())
+    }
+
+    fn render_query_client(&self, out: &mut String) {
+        let Some(query) = &self.query else {
+            return;
+        };
+
+        writeln!(out, "\nexport class {}QueryClient {{", self.name).unwrap();
+        writeln!(
+            out,
+            "  constructor(readonly client: CosmWasmQueryClient, readonly contractAddress: string) {{}}"
+        )
+        .unwrap();
+        for (msg, payload) in messages(query) {
+            let response = self
+                .responses
+                .get(&msg)
+                .map(|resp| type_name(&root_name(resp)))
+                .unwrap_or_else(|| "unknown".to_owned());
+            let (params, args) = method_params(&payload);
+
+            writeln!(
+                out,
+                "\n  async {}({}): Promise<{}> {{\n    return this.client.queryContractSmart(this.contractAddress, {{ {}: {} }});\n  }}",
+                to_camel_case(&msg),
+                params,
+                response,
+                msg,
+                args
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+    }
+
+    fn render_message_composer(&self, out: &mut String) {
+        let Some(exec) = &self.exec else {
+            return;
+        };
+
+        writeln!(out, "\nexport class {}MessageComposer {{", self.name).unwrap();
+        writeln!(
+            out,
+            "  constructor(readonly sender: string, readonly contractAddress: string) {{}}"
+        )
+        .unwrap();
+        for (msg, payload) in messages(exec) {
+            let (params, args) = method_params(&payload);
+            let params = match params.is_empty() {
+                true => "funds: Funds = []".to_owned(),
+                false => format!("{}, funds: Funds = []", params),
+            };
+
+            writeln!(
+                out,
+                "\n  {}({}): MsgExecuteContractEncodeObject {{\n    return executeContract(this.sender, this.contractAddress, {{ {}: {} }}, funds);\n  }}",
+                to_camel_case(&msg),
+                params,
+                msg,
+                args
+            )
+            .unwrap();
+        }
+        out.push_str("}\n");
+    }
+}
+
+const PRELUDE: &str = r#"
+export type Funds = { denom: string; amount: string }[];
+
+export interface CosmWasmQueryClient {
+  queryContractSmart(address: string, queryMsg: unknown): Promise<any>;
+}
+
+export interface MsgExecuteContractEncodeObject {
+  typeUrl: "/cosmwasm.wasm.v1.MsgExecuteContract";
+  value: { sender: string; contract: string; msg: Uint8Array; funds: Funds };
+}
+
+function executeContract(
+  sender: string,
+  contract: string,
+  msg: unknown,
+  funds: Funds,
+): MsgExecuteContractEncodeObject {
+  return {
+    typeUrl: "/cosmwasm.wasm.v1.MsgExecuteContract",
+    value: { sender, contract, msg: new TextEncoder().encode(JSON.stringify(msg)), funds },
+  };
+}
+"#;
+
+/// Splits root schema into its own type name, schema and definitions
+fn split_root(root: &RootSchema) -> (String, Value, BTreeMap<String, Value>) {
+    let mut schema = ::serde_json::to_value(root).expect("Schema is always serializable");
+    let definitions = schema
+        .as_object_mut()
+        .and_then(|schema| schema.remove("definitions"))
+        .and_then(|defs| defs.as_object().cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, schema)| (type_name(&name), schema))
+        .collect();
+
+    (type_name(&root_name(root)), schema, definitions)
+}
+
+fn root_name(root: &RootSchema) -> String {
+    root.schema
+        .metadata
+        .as_ref()
+        .and_then(|meta| meta.title.clone())
+        .unwrap_or_else(|| "Unknown".to_owned())
+}
+
+/// Returns method parameters declaration and the message payload passed to the contract
+fn method_params(payload: &Value) -> (String, String) {
+    let has_fields = payload
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|props| !props.is_empty());
+
+    match has_fields {
+        true => (format!("args: {}", ts_type(payload)), "args".to_owned()),
+        false => (String::new(), "{}".to_owned()),
+    }
+}
+
+fn ts_type(schema: &Value) -> String {
+    let schema = match schema {
+        Value::Object(schema) => schema,
+        Value::Bool(false) => return "never".to_owned(),
+        _ => return "unknown".to_owned(),
+    };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return type_name(reference.trim_start_matches("#/definitions/"));
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        return join(all_of.iter().map(ts_type), " & ");
+    }
+
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        return join(variants.iter().map(ts_type), " | ");
+    }
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return join(values.iter().map(Value::to_string), " | ");
+    }
+
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+
+    match schema.get("type") {
+        Some(Value::String(ty)) => primitive_type(ty, schema),
+        Some(Value::Array(types)) => join(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|ty| primitive_type(ty, schema)),
+            " | ",
+        ),
+        _ => "unknown".to_owned(),
+    }
+}
+
+fn primitive_type(ty: &str, schema: &::serde_json::Map<String, Value>) -> String {
+    match ty {
+        "null" => "null".to_owned(),
+        "boolean" => "boolean".to_owned(),
+        "integer" | "number" => "number".to_owned(),
+        "string" => "string".to_owned(),
+        "array" => match schema.get("items") {
+            Some(Value::Array(items)) => format!("[{}]", join(items.iter().map(ts_type), ", ")),
+            Some(items) => format!("{}[]", parenthesize(ts_type(items))),
+            None => "unknown[]".to_owned(),
+        },
+        "object" => object_type(schema),
+        _ => "unknown".to_owned(),
+    }
+}
+
+fn object_type(schema: &::serde_json::Map<String, Value>) -> String {
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let required: Vec<_> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    match (properties, schema.get("additionalProperties")) {
+        (Some(properties), _) if !properties.is_empty() => {
+            let fields = properties.iter().map(|(name, ty)| {
+                let optional = if required.contains(&name.as_str()) {
+                    ""
+                } else {
+                    "?"
+                };
+                format!("{}{}: {}", property_name(name), optional, ts_type(ty))
+            });
+            format!("{{ {} }}", fields.collect::<Vec<_>>().join("; "))
+        }
+        (_, Some(additional @ Value::Object(_))) => {
+            format!("Record<string, {}>", ts_type(additional))
+        }
+        _ => "{}".to_owned(),
+    }
+}
+
+fn join(items: impl Iterator<Item = String>, separator: &str) -> String {
+    let items: Vec<_> = items.map(parenthesize).collect();
+    match items.as_slice() {
+        [] => "never".to_owned(),
+        [item] => item.clone(),
+        items => items.join(separator),
+    }
+}
+
+/// Wraps unions and intersections in parentheses, so they can be safely nested
+fn parenthesize(ty: String) -> String {
+    let mut depth = 0;
+    let top_level_operator = ty.chars().any(|c| {
+        match c {
+            '{' | '(' | '[' | '<' => depth += 1,
+            '}' | ')' | ']' | '>' => depth -= 1,
+            _ => (),
+        }
+        depth == 0 && (c == '|' || c == '&')
+    });
+
+    match top_level_operator {
+        true => format!("({})", ty),
+        false => ty,
+    }
+}
+
+fn property_name(name: &str) -> String {
+    let is_ident = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+
+    match is_ident {
+        true => name.to_owned(),
+        false => Value::String(name.to_owned()).to_string(),
+    }
+}
+
+/// Converts schema definition names like `CosmosMsg_for_Empty` to TypeScript type names
+fn type_name(name: &str) -> String {
+    to_pascal_case(name)
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn to_camel_case(name: &str) -> String {
+    let pascal = to_pascal_case(name);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn types() {
+        assert_eq!(ts_type(&json!({"type": "string"})), "string");
+        assert_eq!(
+            ts_type(&json!({"type": ["string", "null"]})),
+            "string | null"
+        );
+        assert_eq!(
+            ts_type(&json!({"type": "array", "items": {"$ref": "#/definitions/Coin"}})),
+            "Coin[]"
+        );
+        assert_eq!(
+            ts_type(&json!({"type": "array", "items": {"type": ["integer", "null"]}})),
+            "(number | null)[]"
+        );
+        assert_eq!(
+            ts_type(&json!({
+                "type": "object",
+                "required": ["addr"],
+                "properties": {
+                    "addr": {"type": "string"},
+                    "expires": {"anyOf": [{"$ref": "#/definitions/Expiration"}, {"type": "null"}]}
+                }
+            })),
+            "{ addr: string; expires?: Expiration | null }"
+        );
+        assert_eq!(
+            ts_type(&json!({"type": "object", "additionalProperties": {"type": "integer"}})),
+            "Record<string, number>"
+        );
+        assert_eq!(
+            ts_type(&json!({"type": "string", "enum": ["a", "b"]})),
+            "\"a\" | \"b\""
+        );
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(type_name("CosmosMsg_for_Empty"), "CosmosMsgForEmpty");
+        assert_eq!(to_pascal_case("cw1-whitelist"), "Cw1Whitelist");
+        assert_eq!(to_camel_case("can_execute"), "canExecute");
+        assert_eq!(property_name("new_count"), "new_count");
+        assert_eq!(property_name("with-dash"), "\"with-dash\"");
+    }
+}
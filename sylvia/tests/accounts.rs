@@ -0,0 +1,92 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{coin, coins};
+use sylvia::multitest::{App, Bech32App};
+
+use crate::treasury::multitest_utils::CodeId;
+
+pub mod treasury {
+    use cosmwasm_std::{BankMsg, Coin, Response, StdResult};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx};
+
+    pub struct Treasury;
+
+    #[contract]
+    impl Treasury {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn pay(&self, ctx: ExecCtx, recipient: String, amount: Coin) -> StdResult<Response> {
+            let recipient = ctx.deps.api.addr_validate(&recipient)?;
+            let msg = BankMsg::Send {
+                to_address: recipient.into_string(),
+                amount: vec![amount],
+            };
+            Ok(Response::new().add_message(msg))
+        }
+    }
+}
+
+#[test]
+fn accounts() {
+    let app = App::<Bech32App>::bech32("juno");
+    let owner = app.account("owner");
+    let alice = app.account("alice");
+    assert!(owner.as_str().starts_with("juno1"));
+    assert_eq!(app.account("owner"), owner);
+    assert_ne!(alice, owner);
+
+    let treasury = CodeId::store_code(&app)
+        .instantiate()
+        .call(owner.as_str())
+        .unwrap();
+
+    app.fund(&owner, &[coin(100, "ujuno"), coin(5, "uatom")]);
+    app.fund(&owner, &coins(50, "ujuno"));
+    app.assert_balances(&owner, &[coin(5, "uatom"), coin(150, "ujuno")]);
+
+    treasury
+        .pay(alice.to_string(), coin(30, "ujuno"))
+        .with_funds(&coins(30, "ujuno"))
+        .call(owner.as_str())
+        .unwrap();
+    assert_eq!(app.balance(&owner, "ujuno"), 120);
+    app.assert_balances(&treasury.contract_addr, &[]);
+    app.assert_balances(&alice, &[coin(30, "ujuno"), coin(0, "uatom")]);
+
+    // Addresses are validated like on the chain
+    treasury
+        .pay("alice".to_owned(), coin(1, "ujuno"))
+        .call(owner.as_str())
+        .unwrap_err();
+    let osmo_alice = App::<Bech32App>::bech32("osmo").account("alice");
+    treasury
+        .pay(osmo_alice.to_string(), coin(1, "ujuno"))
+        .call(owner.as_str())
+        .unwrap_err();
+}
+
+#[test]
+#[should_panic(expected = "Funding alice failed")]
+fn fund_invalid_address() {
+    let app = App::<Bech32App>::bech32("juno");
+    app.fund("alice", &coins(100, "ujuno"));
+}
+
+#[test]
+#[should_panic(expected = "expected [Coin { 10 \"ujuno\" }]")]
+fn assert_balances_mismatch() {
+    let app = App::<Bech32App>::bech32("juno");
+    let alice = app.account("alice");
+    app.fund(&alice, &coins(20, "ujuno"));
+    app.assert_balances(&alice, &coins(10, "ujuno"));
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "mt")]
+
+use std::time::Duration;
+
+use cosmwasm_std::BlockInfo;
+use sylvia::multitest::{App, BLOCK_TIME};
+
+use crate::clock::multitest_utils::CodeId;
+
+pub mod clock {
+    use cosmwasm_std::{BlockInfo, Response, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    pub struct Clock<'a> {
+        ticks: Item<'a, Vec<BlockInfo>>,
+    }
+
+    #[contract]
+    impl Clock<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                ticks: Item::new("ticks"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.ticks.save(ctx.deps.storage, &vec![ctx.env.block])?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn tick(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.ticks.update(ctx.deps.storage, |mut ticks| -> StdResult<_> {
+                ticks.push(ctx.env.block);
+                Ok(ticks)
+            })?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn ticks(&self, ctx: QueryCtx) -> StdResult<Vec<BlockInfo>> {
+            self.ticks.load(ctx.deps.storage)
+        }
+    }
+}
+
+#[test]
+fn advance() {
+    let app = App::<cw_multi_test::App>::default();
+    let start = app.block_info();
+
+    app.advance_blocks(3);
+    let block = app.block_info();
+    assert_eq!(block.height, start.height + 3);
+    assert_eq!(block.time, start.time.plus_seconds(3 * BLOCK_TIME.as_secs()));
+
+    // Any time passed makes a new block
+    app.advance_time(Duration::from_secs(1));
+    let block = app.block_info();
+    assert_eq!(block.height, start.height + 4);
+    assert_eq!(block.time, start.time.plus_seconds(16));
+
+    app.advance_time(Duration::from_secs(3600));
+    let block = app.block_info();
+    assert_eq!(block.height, start.height + 4 + 720);
+    assert_eq!(block.time, start.time.plus_seconds(3616));
+
+    app.advance_time(Duration::ZERO);
+    assert_eq!(app.block_info(), block);
+}
+
+#[test]
+#[should_panic(expected = "Block time overflows u64 nanoseconds")]
+fn advance_overflow() {
+    let app = App::<cw_multi_test::App>::default();
+    app.advance_blocks(u64::MAX / BLOCK_TIME.as_secs());
+}
+
+#[test]
+fn auto_advance() {
+    let app = App::<cw_multi_test::App>::default();
+    let start = app.block_info();
+    app.auto_advance_blocks(true);
+
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    contract.tick().call("owner").unwrap();
+    contract.tick().simulate("owner").unwrap();
+    contract.ticks().unwrap();
+    contract.tick().call("owner").unwrap();
+
+    let block_at = |blocks: u64| BlockInfo {
+        height: start.height + blocks,
+        time: start.time.plus_seconds(blocks * BLOCK_TIME.as_secs()),
+        chain_id: start.chain_id.clone(),
+    };
+    assert_eq!(
+        contract.ticks().unwrap(),
+        [block_at(1), block_at(2), block_at(3)]
+    );
+
+    app.auto_advance_blocks(false);
+    contract.tick().call("owner").unwrap();
+    assert_eq!(contract.ticks().unwrap()[3], block_at(3));
+}
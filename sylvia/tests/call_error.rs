@@ -0,0 +1,171 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{coins, Addr, StdError};
+use sylvia::multitest::{App, CallError};
+
+use crate::forwarder::{multitest_utils::CodeId as ForwarderCodeId, ForwarderError};
+use crate::vault::{multitest_utils::CodeId as VaultCodeId, VaultError};
+
+pub mod vault {
+    use cosmwasm_std::{Response, StdError};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx};
+    use thiserror::Error;
+
+    #[derive(Error, Debug, PartialEq)]
+    pub enum VaultError {
+        #[error("{0}")]
+        Std(#[from] StdError),
+
+        #[error("Vault is locked")]
+        Locked,
+    }
+
+    pub struct Vault;
+
+    #[contract]
+    #[error(VaultError)]
+    impl Vault {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx) -> Result<Response, VaultError> {
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn withdraw(&self, _ctx: ExecCtx) -> Result<Response, VaultError> {
+            Err(VaultError::Locked)
+        }
+    }
+}
+
+pub mod forwarder {
+    use cosmwasm_std::{to_binary, Response, StdError, WasmMsg};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx};
+    use thiserror::Error;
+
+    use crate::vault::ExecMsg as VaultExecMsg;
+
+    #[derive(Error, Debug, PartialEq)]
+    pub enum ForwarderError {
+        #[error("{0}")]
+        Std(#[from] StdError),
+    }
+
+    pub struct Forwarder;
+
+    #[contract]
+    #[error(ForwarderError)]
+    impl Forwarder {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx) -> Result<Response, ForwarderError> {
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn withdraw(&self, _ctx: ExecCtx, vault: String) -> Result<Response, ForwarderError> {
+            let msg = WasmMsg::Execute {
+                contract_addr: vault,
+                msg: to_binary(&VaultExecMsg::Withdraw {})?,
+                funds: vec![],
+            };
+            Ok(Response::new().add_message(msg))
+        }
+    }
+}
+
+#[test]
+fn contract_error() {
+    let app = App::<cw_multi_test::App>::default();
+    let vault = VaultCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    let err = vault.withdraw().call("owner").unwrap_err();
+    assert_eq!(err, VaultError::Locked);
+    assert_eq!(err.contract(), Some(&VaultError::Locked));
+    assert_eq!(err.to_string(), "Vault is locked");
+}
+
+#[test]
+fn nested_error() {
+    let app = App::<cw_multi_test::App>::default();
+    let vault = VaultCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    let forwarder = ForwarderCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    let err = forwarder
+        .withdraw(vault.contract_addr.to_string())
+        .call("owner")
+        .unwrap_err();
+    assert!(matches!(err, CallError::Nested(_)));
+    assert_eq!(err.contract(), None);
+    assert_eq!(err.downcast_ref::<VaultError>(), Some(&VaultError::Locked));
+
+    // Whole call chain is reported
+    let report = err.to_string();
+    assert!(report.contains("sender: owner"));
+    assert!(report.contains(&format!("sender: {}", forwarder.contract_addr)));
+    assert!(report.ends_with(&format!(
+        "contract {} failed: Vault is locked",
+        vault.contract_addr
+    )));
+}
+
+#[test]
+fn framework_error() {
+    let owner = Addr::unchecked("owner");
+    let app = App::new(cw_multi_test::App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(10, "uatom"))
+            .unwrap();
+    }));
+    let forwarder = ForwarderCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    // Funds transfer fails before the contract is called
+    let err = forwarder
+        .withdraw("vault".to_owned())
+        .with_funds(&coins(20, "uatom"))
+        .call("owner")
+        .unwrap_err();
+    assert!(matches!(err, CallError::Framework(_)));
+    assert!(matches!(
+        err.downcast_ref::<StdError>(),
+        Some(StdError::Overflow { .. })
+    ));
+
+    // Message sent by the contract fails
+    let err = forwarder
+        .withdraw("vault".to_owned())
+        .call("owner")
+        .unwrap_err();
+    assert!(matches!(err, CallError::Nested(_)));
+    assert_eq!(err.downcast_ref::<ForwarderError>(), None);
+
+    let err = ForwarderCodeId::store_code(&app)
+        .instantiate()
+        .with_funds(&coins(20, "uatom"))
+        .call("owner")
+        .unwrap_err();
+    assert!(matches!(err, CallError::Framework(_)));
+}
@@ -0,0 +1,208 @@
+#![cfg(feature = "cli")]
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Binary;
+use sylvia::cli::Cli;
+
+#[cw_serde]
+pub struct Config {
+    pub admin: String,
+    pub limit: u32,
+}
+
+pub mod limits {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::interface;
+    use sylvia::types::{ExecCtx, QueryCtx};
+
+    #[interface]
+    pub trait Limits {
+        type Error: From<StdError>;
+
+        #[msg(exec)]
+        fn set_limit(&self, ctx: ExecCtx, limit: u32, expires: Option<u64>) -> StdResult<Response>;
+
+        #[msg(query)]
+        fn limit(&self, ctx: QueryCtx) -> StdResult<u32>;
+    }
+}
+
+pub mod contract {
+    use cosmwasm_std::{Response, StdError, StdResult, Uint128};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    use crate::limits::Limits;
+    use crate::Config;
+
+    pub struct Contract;
+
+    #[contract]
+    #[messages(crate::limits as Limits)]
+    impl Contract {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx, config: Config) -> StdResult<Response> {
+            let _ = config;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn transfer(
+            &self,
+            _ctx: ExecCtx,
+            recipient: String,
+            amount: Uint128,
+            memo: Option<String>,
+        ) -> StdResult<Response> {
+            let _ = (recipient, amount, memo);
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn balance(&self, _ctx: QueryCtx, address: String) -> StdResult<Uint128> {
+            let _ = address;
+            Ok(Uint128::zero())
+        }
+    }
+
+    impl Limits for Contract {
+        type Error = StdError;
+
+        fn set_limit(
+            &self,
+            _ctx: ExecCtx,
+            _limit: u32,
+            _expires: Option<u64>,
+        ) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        fn limit(&self, _ctx: QueryCtx) -> StdResult<u32> {
+            Ok(0)
+        }
+    }
+}
+
+fn cli() -> Cli {
+    Cli::new("contract")
+        .instantiate::<contract::InstantiateMsg>()
+        .exec::<contract::ContractExecMsg>()
+        .query::<contract::ContractQueryMsg>()
+}
+
+fn run(args: &[&str]) -> Result<String, sylvia::clap::Error> {
+    cli().try_run_from(std::iter::once("contract").chain(args.iter().copied()))
+}
+
+#[test]
+fn build_messages() {
+    assert_eq!(
+        run(&["instantiate", "--config", r#"{"admin":"owner","limit":5}"#]).unwrap(),
+        r#"{"config":{"admin":"owner","limit":5}}"#
+    );
+    assert_eq!(
+        run(&[
+            "exec",
+            "transfer",
+            "--recipient",
+            "addr0001",
+            "--amount",
+            "100"
+        ])
+        .unwrap(),
+        r#"{"transfer":{"recipient":"addr0001","amount":"100","memo":null}}"#
+    );
+    assert_eq!(
+        run(&[
+            "exec",
+            "transfer",
+            "--recipient",
+            "12",
+            "--amount",
+            "1",
+            "--memo",
+            "hi"
+        ])
+        .unwrap(),
+        r#"{"transfer":{"recipient":"12","amount":"1","memo":"hi"}}"#
+    );
+    assert_eq!(
+        run(&["exec", "set_limit", "--limit", "7"]).unwrap(),
+        r#"{"set_limit":{"limit":7,"expires":null}}"#
+    );
+    assert_eq!(
+        run(&["query", "balance", "--address", "addr0001"]).unwrap(),
+        r#"{"balance":{"address":"addr0001"}}"#
+    );
+    assert_eq!(run(&["query", "limit"]).unwrap(), r#"{"limit":{}}"#);
+    assert_eq!(
+        run(&["query", "sylvia_info"]).unwrap(),
+        r#"{"sylvia_info":{}}"#
+    );
+}
+
+#[test]
+fn encode_execute() {
+    let encoded = run(&[
+        "exec",
+        "--encode",
+        "--sender",
+        "sender",
+        "--contract",
+        "contract",
+        "--funds",
+        "100uatom",
+        "set_limit",
+        "--limit",
+        "7",
+    ])
+    .unwrap();
+    let encoded = Binary::from_base64(&encoded).unwrap();
+
+    let msg = br#"{"set_limit":{"limit":7,"expires":null}}"#;
+    let mut expected = vec![0x0a, 6];
+    expected.extend_from_slice(b"sender");
+    expected.extend_from_slice(&[0x12, 8]);
+    expected.extend_from_slice(b"contract");
+    expected.extend_from_slice(&[0x1a, msg.len() as u8]);
+    expected.extend_from_slice(msg);
+    expected.extend_from_slice(&[0x2a, 12, 0x0a, 5]);
+    expected.extend_from_slice(b"uatom");
+    expected.extend_from_slice(&[0x12, 3]);
+    expected.extend_from_slice(b"100");
+    assert_eq!(encoded.as_slice(), expected.as_slice());
+}
+
+#[test]
+fn invalid_arguments() {
+    use sylvia::clap::error::ErrorKind;
+
+    let err = run(&[
+        "exec",
+        "transfer",
+        "--recipient",
+        "addr0001",
+        "--amount",
+        "x",
+    ])
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::ValueValidation);
+    assert!(err.to_string().contains("--amount"));
+
+    let err = run(&["exec", "transfer", "--recipient", "addr0001"]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+    let err = run(&["instantiate", "--config", r#"{"admin":"owner"}"#]).unwrap_err();
+    assert!(err.to_string().contains("missing field `limit`"));
+
+    let err = run(&["exec", "--encode", "transfer"]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+
+    let err = run(&["exec", "mint"]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidSubcommand);
+}
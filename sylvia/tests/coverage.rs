@@ -0,0 +1,174 @@
+#![cfg(feature = "mt")]
+
+use sylvia::multitest::{App, MessageCoverage};
+
+use crate::counter::multitest_utils::CodeId;
+
+pub mod reset {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::interface;
+    use sylvia::types::ExecCtx;
+
+    #[interface]
+    pub trait Reset {
+        type Error: From<StdError>;
+
+        #[msg(exec)]
+        fn reset(&self, ctx: ExecCtx) -> StdResult<Response>;
+    }
+}
+
+pub mod counter {
+    use cosmwasm_std::{ensure, Response, StdError, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx};
+
+    use crate::reset::Reset;
+
+    pub struct Counter<'a> {
+        count: Item<'a, u32>,
+    }
+
+    #[contract]
+    #[messages(crate::reset as Reset)]
+    impl Counter<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                count: Item::new("count"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx, limit: u32) -> StdResult<Response> {
+            let count = self.count.load(ctx.deps.storage)? + 1;
+            ensure!(count <= limit, StdError::generic_err("Limit reached"));
+            self.count.save(ctx.deps.storage, &count)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn decrement(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.count.update(ctx.deps.storage, |count| {
+                count
+                    .checked_sub(1)
+                    .ok_or_else(|| StdError::generic_err("Underflow"))
+            })?;
+            Ok(Response::new())
+        }
+
+        #[msg(migrate)]
+        fn migrate(&self, _ctx: MigrateCtx) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.count.load(ctx.deps.storage)
+        }
+    }
+
+    impl Reset for Counter<'_> {
+        type Error = StdError;
+
+        fn reset(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+    }
+}
+
+#[test]
+fn coverage() {
+    let app = App::<cw_multi_test::App>::default();
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    contract.increment(1).call("owner").unwrap();
+    contract.increment(1).call("owner").unwrap_err();
+    contract.count().unwrap();
+    contract.decrement().call("owner").unwrap();
+
+    let coverage = app.coverage();
+    let counter = &coverage.contracts["Counter"];
+    assert_eq!(
+        counter.messages["exec increment"],
+        MessageCoverage {
+            calls: 2,
+            failures: 1
+        }
+    );
+    assert_eq!(
+        counter.untested().collect::<Vec<_>>(),
+        ["exec reset", "migrate"]
+    );
+    assert_eq!(
+        counter.errors_not_hit().collect::<Vec<_>>(),
+        ["exec decrement", "instantiate", "query count"]
+    );
+    assert_eq!(
+        coverage.to_string(),
+        "Counter: 4 of 6 messages tested\n  \
+         not tested: exec reset, migrate\n  \
+         never failed: exec decrement, instantiate, query count\n"
+    );
+}
+
+#[test]
+fn glue_messages() {
+    assert_eq!(
+        counter::ContractExecMsg::messages(),
+        ["reset", "decrement", "increment"]
+    );
+    assert_eq!(
+        counter::ContractQueryMsg::messages(),
+        ["count", "sylvia_info"]
+    );
+}
+
+#[test]
+fn report_file() {
+    let path = std::env::temp_dir()
+        .join(format!("sylvia-coverage-{}", std::process::id()))
+        .join("coverage.txt");
+
+    let app = App::<cw_multi_test::App>::default();
+    let code_id = CodeId::store_code(&app);
+    code_id.instantiate().call("owner").unwrap();
+
+    let other_app = App::<cw_multi_test::App>::default();
+    let contract = CodeId::store_code(&other_app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    contract.decrement().call("owner").unwrap_err();
+
+    let mut coverage = app.coverage();
+    coverage.merge(&other_app.coverage());
+    assert_eq!(
+        coverage.contracts["Counter"].messages["instantiate"],
+        MessageCoverage {
+            calls: 2,
+            failures: 0
+        }
+    );
+
+    coverage.write_report(&path).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(report, coverage.to_string());
+    assert!(report.contains("Counter: 2 of 6 messages tested"));
+
+    app.write_coverage_report(&path).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    assert!(report.contains("Counter: 1 of 6 messages tested"));
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -167,3 +167,108 @@ fn dispatch() {
     assert_eq!(*contract.execs.borrow(), 3);
     assert_eq!(*contract.queries.borrow(), 2);
 }
+
+#[test]
+fn glue_deserialization() {
+    use cosmwasm_std::from_slice;
+
+    let msg: ContractExecMsg = from_slice(
+        br#"{ "argumented_execution": { "addr": "addr1", "coef": "0.3", "desc": "True" } }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        msg,
+        ContractExecMsg::Interface(interface::ExecMsg::ArgumentedExecution {
+            addr: Addr::unchecked("addr1"),
+            coef: Decimal::percent(30),
+            desc: "True".to_owned(),
+        })
+    );
+
+    let msg: ContractQueryMsg = from_slice(br#"{ "no_args_query": {} }"#).unwrap();
+    assert_eq!(
+        msg,
+        ContractQueryMsg::Interface(interface::QueryMsg::NoArgsQuery {})
+    );
+
+    let err = from_slice::<ContractExecMsg>(br#"{ "unknown": {} }"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Unsupported message received: unknown. Messages supported by this contract: argumented_execution, no_args_execution"));
+
+    let err = from_slice::<ContractExecMsg>(br#"{ "no_args_execution": {}, "unknown": {} }"#)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Expected exactly one message. Received 2"));
+
+    let err = from_slice::<ContractExecMsg>(br#"{}"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Expected exactly one message. Received 0"));
+
+    // Payload errors are reported as for the plain message
+    let err = from_slice::<ContractExecMsg>(br#"{ "argumented_execution": { "addr": "addr1" } }"#)
+        .unwrap_err();
+    assert!(err.to_string().contains("missing field `coef`"), "{}", err);
+
+    assert!(from_slice::<ContractExecMsg>(br#""no_args_execution""#).is_err());
+}
+
+#[test]
+fn glue_deserialization_errors() {
+    use sylvia::de::{from_slice, MessageError};
+
+    let err = from_slice::<ContractExecMsg>(br#"{ "no_args_executon": {} }"#).unwrap_err();
+    assert_eq!(
+        err,
+        MessageError::UnsupportedMessage {
+            received: "no_args_executon".to_owned(),
+            suggestion: Some("no_args_execution".to_owned()),
+            supported: vec![
+                "argumented_execution".to_owned(),
+                "no_args_execution".to_owned()
+            ],
+        }
+    );
+    assert!(err
+        .to_string()
+        .contains("Did you mean `no_args_execution`?"));
+
+    // Entry points report the path to the invalid field too
+    let msg = br#"{ "argumented_execution": { "addr": "addr1", "coef": "abc", "desc": "True" } }"#;
+    let err = cosmwasm_std::from_slice::<ContractExecMsg>(msg).unwrap_err();
+    assert!(
+        err.to_string().contains("argumented_execution.coef: "),
+        "{}",
+        err
+    );
+    let MessageError::InvalidMessage { path, .. } = from_slice::<ContractExecMsg>(msg).unwrap_err()
+    else {
+        panic!("Unexpected error: {}", err);
+    };
+    assert_eq!(path, "argumented_execution.coef");
+
+    let err = from_slice::<ContractQueryMsg>(br#"{ "argumented_query": {} }"#).unwrap_err();
+    assert_eq!(
+        err,
+        MessageError::InvalidMessage {
+            path: "argumented_query".to_owned(),
+            error: "missing field `user`".to_owned(),
+        }
+    );
+
+    let err = from_slice::<ContractQueryMsg>(br#"{ "no_args_query": {}, "sylvia_info": {} }"#)
+        .unwrap_err();
+    assert_eq!(err, MessageError::MessagesCount { received: 2 });
+
+    let err = from_slice::<ContractExecMsg>(br#""no_args_execution""#).unwrap_err();
+    assert!(matches!(err, MessageError::InvalidMessage { path, .. } if path.is_empty()));
+
+    assert_eq!(
+        from_slice::<ContractExecMsg>(br#"{ "no_args_execution": {} }"#),
+        Ok(ContractExecMsg::Interface(
+            interface::ExecMsg::NoArgsExecution {}
+        ))
+    );
+}
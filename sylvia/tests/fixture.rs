@@ -0,0 +1,154 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{coins, Addr, Empty};
+use sylvia::multitest::{App, Fixture, Mock};
+
+use crate::forwarder::multitest_utils::{CodeId as ForwarderCodeId, ForwarderProxy};
+use crate::vault::multitest_utils::{CodeId as VaultCodeId, VaultProxy};
+
+pub mod vault {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::contract;
+    use sylvia::types::InstantiateCtx;
+
+    pub struct Vault;
+
+    #[contract]
+    impl Vault {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            if ctx.info.funds.is_empty() {
+                return Err(StdError::generic_err("Empty vault"));
+            }
+            Ok(Response::new())
+        }
+    }
+}
+
+pub mod forwarder {
+    use cosmwasm_std::{Addr, Response, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{InstantiateCtx, QueryCtx};
+
+    pub struct Forwarder<'a> {
+        targets: Item<'a, Vec<Addr>>,
+    }
+
+    #[contract]
+    impl Forwarder<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                targets: Item::new("targets"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx, targets: Vec<String>) -> StdResult<Response> {
+            let targets = targets
+                .iter()
+                .map(|target| ctx.deps.api.addr_validate(target))
+                .collect::<StdResult<_>>()?;
+            self.targets.save(ctx.deps.storage, &targets)?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn targets(&self, ctx: QueryCtx) -> StdResult<Vec<Addr>> {
+            self.targets.load(ctx.deps.storage)
+        }
+    }
+}
+
+struct Contracts<'app> {
+    vault: VaultProxy<'app, cw_multi_test::App>,
+    forwarder: ForwarderProxy<'app, cw_multi_test::App>,
+    sink: Addr,
+}
+
+#[test]
+fn deployment() {
+    let app = App::<cw_multi_test::App>::default();
+    let vault_code_id = VaultCodeId::store_code(&app);
+    let sink = Mock::<Empty, Empty>::new().store_code(&app);
+
+    let contracts = Fixture::new(&app)
+        .fund("owner", &coins(100, "ujuno"))
+        .contract("vault", |_| {
+            vault_code_id
+                .instantiate()
+                .with_funds(&coins(40, "ujuno"))
+                .call("owner")
+        })
+        .contract("sink", |_| sink.instantiate("owner"))
+        .contract("forwarder", |deployment| {
+            ForwarderCodeId::store_code(deployment.app())
+                .instantiate(vec![
+                    deployment.addr("vault").into_string(),
+                    deployment.addr("sink").into_string(),
+                ])
+                .call("owner")
+        })
+        .build(|deployment| Contracts {
+            vault: deployment.proxy("vault"),
+            forwarder: deployment.proxy("forwarder"),
+            sink: deployment.addr("sink"),
+        });
+
+    assert_eq!(
+        contracts.forwarder.targets().unwrap(),
+        [contracts.vault.contract_addr.clone(), contracts.sink]
+    );
+    app.assert_balances("owner", &coins(60, "ujuno"));
+    app.assert_balances(contracts.vault.contract_addr.as_str(), &coins(40, "ujuno"));
+}
+
+#[test]
+#[should_panic(expected = "Contract `vault` is not deployed yet")]
+fn dependency_declared_later() {
+    let app = App::<cw_multi_test::App>::default();
+
+    Fixture::new(&app)
+        .contract("forwarder", |deployment| {
+            ForwarderCodeId::store_code(deployment.app())
+                .instantiate(vec![deployment.addr("vault").into_string()])
+                .call("owner")
+        })
+        .contract("vault", |deployment| {
+            VaultCodeId::store_code(deployment.app())
+                .instantiate()
+                .call("owner")
+        })
+        .build(|_| ());
+}
+
+#[test]
+#[should_panic(expected = "Deploying `vault` failed: Generic error: Empty vault")]
+fn failed_deployment() {
+    let app = App::<cw_multi_test::App>::default();
+
+    Fixture::new(&app)
+        .contract("vault", |deployment| {
+            VaultCodeId::store_code(deployment.app())
+                .instantiate()
+                .call("owner")
+        })
+        .build(|_| ());
+}
+
+#[test]
+#[should_panic(expected = "Contract `vault` is already declared in the fixture")]
+fn duplicated_contract() {
+    let app = App::<cw_multi_test::App>::default();
+    let code_id = VaultCodeId::store_code(&app);
+
+    let _ = Fixture::new(&app)
+        .contract("vault", |_| code_id.instantiate().call("owner"))
+        .contract("vault", |_| code_id.instantiate().call("owner"));
+}
@@ -0,0 +1,210 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{Response, StdResult, Uint128};
+use cw_storage_plus::Item;
+use sylvia::contract;
+use sylvia::types::InstantiateCtx;
+
+pub mod counter {
+    sylvia::import_schema!("tests/schemas/counter.json");
+}
+
+pub mod mock {
+    use cosmwasm_std::{Addr, Response, StdError, StdResult, Uint128};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, QueryCtx};
+
+    use crate::counter::{Action, Config, CountResponse, Counter, Entry};
+
+    #[contract(module=super)]
+    #[messages(crate::counter as Counter)]
+    impl Counter for super::CounterMock {
+        type Error = StdError;
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx) -> StdResult<Response> {
+            let count = self.count.load(ctx.deps.storage)? + Uint128::one();
+            self.count.save(ctx.deps.storage, &count)?;
+            self.record(ctx, Action::Increment)
+        }
+
+        #[msg(exec)]
+        fn set_count(
+            &self,
+            ctx: ExecCtx,
+            count: Uint128,
+            memo: Option<String>,
+        ) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &count)?;
+            self.record(ctx, Action::Set { count })
+                .map(|resp| resp.add_attributes(memo.map(|memo| ("memo", memo))))
+        }
+
+        #[msg(exec)]
+        fn update_config(&self, ctx: ExecCtx, config: Config) -> StdResult<Response> {
+            self.config.save(ctx.deps.storage, &config)?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<CountResponse> {
+            let count = self.count.load(ctx.deps.storage)?;
+            Ok(CountResponse { count })
+        }
+
+        #[msg(query)]
+        fn owner(&self, ctx: QueryCtx) -> StdResult<Addr> {
+            let config = self.config.may_load(ctx.deps.storage)?;
+            Ok(config.map_or_else(|| Addr::unchecked(""), |config| config.owner))
+        }
+
+        #[msg(query)]
+        fn history(
+            &self,
+            ctx: QueryCtx,
+            limit: Option<u32>,
+            start_after: Option<u64>,
+        ) -> StdResult<Vec<Entry>> {
+            let history = self.history.load(ctx.deps.storage)?;
+            Ok(history
+                .into_iter()
+                .filter(|entry| Some(entry.height) > start_after)
+                .take(limit.unwrap_or(u32::MAX) as usize)
+                .collect())
+        }
+    }
+
+    impl super::CounterMock {
+        fn record(&self, ctx: ExecCtx, action: Action) -> StdResult<Response> {
+            let height = ctx.env.block.height;
+            self.history.update(ctx.deps.storage, |mut history| {
+                history.push(Entry { action, height });
+                Ok::<_, StdError>(history)
+            })?;
+            Ok(Response::new())
+        }
+    }
+}
+
+pub struct CounterMock {
+    count: Item<'static, Uint128>,
+    config: Item<'static, counter::Config>,
+    history: Item<'static, Vec<counter::Entry>>,
+}
+
+#[contract]
+#[messages(counter as Counter)]
+impl CounterMock {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            count: Item::new("count"),
+            config: Item::new("config"),
+            history: Item::new("history"),
+        }
+    }
+
+    #[msg(instantiate)]
+    fn instantiate(&self, ctx: InstantiateCtx, initial: Uint128) -> StdResult<Response> {
+        self.count.save(ctx.deps.storage, &initial)?;
+        self.history.save(ctx.deps.storage, &vec![])?;
+        Ok(Response::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{from_slice, to_vec, Addr, Uint128};
+    use sylvia::multitest::App;
+
+    use crate::counter::{self, Action, Config, Entry, Limits, Mode};
+    use crate::mock::test_utils::Counter;
+    use crate::multitest_utils::CodeId;
+
+    #[test]
+    fn messages_format() {
+        let msg = counter::ExecMsg::set_count(Uint128::new(5), None);
+        assert_eq!(
+            to_vec(&msg).unwrap(),
+            br#"{"set_count":{"count":"5","memo":null}}"#
+        );
+
+        let msg: counter::ExecMsg = from_slice(br#"{"increment":{}}"#).unwrap();
+        assert_eq!(msg, counter::ExecMsg::Increment {});
+
+        // Fields which are not valid identifiers keep their JSON names
+        let msg = counter::QueryMsg::history(None, Some(3));
+        assert_eq!(
+            to_vec(&msg).unwrap(),
+            br#"{"history":{"limit":null,"startAfter":3}}"#
+        );
+
+        let config: Config = from_slice(
+            br#"{"owner":"owner","mode":"owner_only","limits":{"max":"10","self":true,"type":1}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                owner: Addr::unchecked("owner"),
+                mode: Mode::OwnerOnly,
+                limits: Some(Limits {
+                    max: Uint128::new(10),
+                    self_: Some(true),
+                    r#type: Some(1)
+                }),
+            }
+        );
+
+        let entries: Vec<Entry> = from_slice(
+            br#"[{"action":"increment","height":1},{"action":{"set":{"count":"2"}},"height":2}]"#,
+        )
+        .unwrap();
+        assert_eq!(entries[0].action, Action::Increment);
+        assert_eq!(
+            entries[1].action,
+            Action::Set {
+                count: Uint128::new(2)
+            }
+        );
+
+        // Unknown fields are rejected as by the original contract
+        assert!(from_slice::<Config>(br#"{"owner":"owner","mode":"open","extra":1}"#).is_err());
+    }
+
+    #[test]
+    fn imported_interface() {
+        let app = App::default();
+        let code_id = CodeId::store_code(&app);
+
+        let owner = "owner";
+
+        let contract = code_id.instantiate(Uint128::new(1)).call(owner).unwrap();
+        let proxy = contract.counter_proxy();
+
+        proxy.increment().call(owner).unwrap();
+        proxy
+            .set_count(Uint128::new(10), Some("memo".to_owned()))
+            .call(owner)
+            .unwrap();
+        proxy
+            .update_config(Config {
+                owner: Addr::unchecked(owner),
+                mode: Mode::Open,
+                limits: None,
+            })
+            .call(owner)
+            .unwrap();
+
+        assert_eq!(proxy.count().unwrap().count, Uint128::new(10));
+        assert_eq!(proxy.owner().unwrap(), Addr::unchecked(owner));
+
+        // Imported querier can be used with any contract exposing the schema
+        let mt_app = app.app();
+        let querier_wrapper = mt_app.wrap();
+        let querier = counter::BoundQuerier::borrowed(&contract.contract_addr, &querier_wrapper);
+        let history = counter::Querier::history(&querier, Some(1), None).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].action, Action::Increment);
+    }
+}
@@ -0,0 +1,240 @@
+use cosmwasm_schema::schema_for;
+use cosmwasm_std::{Response, StdResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sylvia::contract;
+use sylvia::types::InstantiateCtx;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+pub struct CountResponse {
+    pub count: u64,
+}
+
+pub mod counter {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::types::{ExecCtx, QueryCtx};
+    use sylvia::{contract, interface};
+
+    use crate::CountResponse;
+
+    #[interface]
+    pub trait Counter {
+        type Error: From<StdError>;
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<CountResponse>;
+
+        #[msg(exec)]
+        fn set_count(&self, ctx: ExecCtx, new_count: u64) -> StdResult<Response>;
+    }
+
+    #[contract(module=super)]
+    #[messages(crate::counter as Counter)]
+    impl Counter for super::CounterContract {
+        type Error = StdError;
+
+        #[msg(query)]
+        fn count(&self, _ctx: QueryCtx) -> StdResult<CountResponse> {
+            Ok(CountResponse::default())
+        }
+
+        #[msg(exec)]
+        fn set_count(&self, _ctx: ExecCtx, _new_count: u64) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+    }
+}
+
+pub mod renamed {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::interface;
+    use sylvia::types::{ExecCtx, QueryCtx};
+
+    use crate::CountResponse;
+
+    #[interface]
+    pub trait Renamed {
+        type Error: From<StdError>;
+
+        /// Doc comments don't affect the interface id
+        #[msg(exec)]
+        fn set_count(&self, ctx: ExecCtx, new_count: std::primitive::u64) -> StdResult<Response>;
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<CountResponse>;
+    }
+}
+
+pub mod changed {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::interface;
+    use sylvia::types::{ExecCtx, QueryCtx};
+
+    use crate::CountResponse;
+
+    #[interface]
+    pub trait Changed {
+        type Error: From<StdError>;
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<CountResponse>;
+
+        #[msg(exec)]
+        fn set_count(&self, ctx: ExecCtx, new_count: u32) -> StdResult<Response>;
+    }
+}
+
+pub struct CounterContract;
+
+#[contract]
+#[messages(counter as Counter)]
+impl CounterContract {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    #[msg(instantiate)]
+    fn instantiate(&self, _ctx: InstantiateCtx) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+}
+
+pub mod empty {
+    use cosmwasm_std::{Response, StdResult};
+    use sylvia::contract;
+    use sylvia::types::InstantiateCtx;
+
+    pub struct EmptyContract;
+
+    #[contract]
+    impl EmptyContract {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+    }
+}
+
+#[test]
+fn interface_api() {
+    use sylvia::types::Interface;
+
+    assert_eq!(counter::InterfaceApi::NAME, "Counter");
+    assert_eq!(counter::InterfaceApi::EXEC_MESSAGES, &["set_count"]);
+    assert_eq!(counter::InterfaceApi::QUERY_MESSAGES, &["count"]);
+    assert_eq!(counter::InterfaceApi::ID.len(), 16);
+}
+
+#[test]
+fn interface_id() {
+    use sylvia::types::Interface;
+
+    // Id depends only on the messages signatures, not on the trait name, methods order,
+    // types paths or doc comments
+    assert_eq!(counter::InterfaceApi::ID, renamed::InterfaceApi::ID);
+    assert_ne!(counter::InterfaceApi::ID, changed::InterfaceApi::ID);
+}
+
+#[test]
+fn schema_digest() {
+    let digest = counter::InterfaceApi::schema().schema_digest;
+
+    assert_eq!(digest.len(), 16);
+    assert_eq!(digest, renamed::InterfaceApi::schema().schema_digest);
+    assert_ne!(digest, changed::InterfaceApi::schema().schema_digest);
+}
+
+#[test]
+fn interface_schema() {
+    use sylvia::types::Interface;
+
+    let schema = counter::InterfaceApi::schema();
+
+    assert_eq!(schema.interface_name, "Counter");
+    assert_eq!(schema.interface_id, counter::InterfaceApi::ID);
+    assert_eq!(schema.exec, schema_for!(counter::ExecMsg));
+    assert_eq!(schema.query, schema_for!(counter::QueryMsg));
+    assert_eq!(schema.responses.keys().collect::<Vec<_>>(), vec!["count"]);
+
+    let rendered: sylvia::schema::InterfaceSchema = serde_json::from_str(&schema.render()).unwrap();
+    assert_eq!(rendered.render(), schema.render());
+}
+
+#[test]
+fn missing_messages() {
+    let schema = counter::InterfaceApi::schema();
+
+    let missing = schema.missing_messages(
+        &schema_for!(ContractExecMsg),
+        &schema_for!(ContractQueryMsg),
+    );
+    assert!(missing.is_empty(), "{:?}", missing);
+
+    let missing =
+        schema.missing_messages(&schema_for!(empty::ExecMsg), &schema_for!(empty::QueryMsg));
+    assert_eq!(missing, vec!["exec.set_count", "query.count"]);
+}
+
+#[test]
+fn sylvia_info() {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{from_binary, from_slice};
+    use sylvia::types::{InterfaceInfo, SylviaInfoResponse};
+
+    let msg: ContractQueryMsg = from_slice(br#"{ "sylvia_info": {} }"#).unwrap();
+    assert_eq!(
+        msg,
+        ContractQueryMsg::Sylvia(sylvia::types::SylviaQueryMsg::SylviaInfo {})
+    );
+
+    let deps = mock_dependencies();
+    let resp = msg
+        .dispatch(&CounterContract::new(), (deps.as_ref(), mock_env()))
+        .unwrap();
+    let resp: SylviaInfoResponse = from_binary(&resp).unwrap();
+
+    assert_eq!(resp.contract, "sylvia");
+    assert_eq!(resp.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        resp.interfaces,
+        vec![InterfaceInfo::new::<counter::InterfaceApi>()]
+    );
+    assert!(resp.supports::<counter::InterfaceApi>());
+    // Same messages, but a different interface
+    assert!(!resp.supports::<renamed::InterfaceApi>());
+    assert!(!resp.supports::<changed::InterfaceApi>());
+}
+
+#[test]
+fn typescript() {
+    use cosmwasm_schema::generate_api;
+    use sylvia::typescript::TsCodegen;
+
+    let interface = TsCodegen::interface(&counter::InterfaceApi::schema()).render();
+    assert!(interface.contains("export type CountResponse = { count: number };"));
+    assert!(interface.contains("export class CounterQueryClient {"));
+    assert!(interface.contains("async count(): Promise<CountResponse> {"));
+    assert!(interface.contains("export class CounterMessageComposer {"));
+    assert!(interface.contains(
+        "setCount(args: { new_count: number }, funds: Funds = []): MsgExecuteContractEncodeObject {"
+    ));
+
+    // Glue messages are flattened, so interface messages are part of the contract client
+    let api = generate_api! {
+        name: "counter-contract",
+        instantiate: InstantiateMsg,
+        execute: ContractExecMsg,
+        query: ContractQueryMsg,
+    };
+    let contract = TsCodegen::contract(&api).render();
+    assert!(contract.contains("export class CounterContractQueryClient {"));
+    assert!(contract.contains("async count(): Promise<CountResponse> {"));
+    assert!(contract.contains("async sylviaInfo(): Promise<SylviaInfoResponse> {"));
+    assert!(contract.contains("setCount(args: { new_count: number }, funds: Funds = [])"));
+}
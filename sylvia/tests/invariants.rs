@@ -0,0 +1,178 @@
+#![cfg(feature = "mt")]
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use anyhow::ensure;
+use cosmwasm_std::Addr;
+use sylvia::multitest::{App, CallError};
+
+use crate::counter::multitest_utils::{CodeId, CounterProxy};
+
+pub mod counter {
+    use cosmwasm_std::{Response, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, MigrateCtx, QueryCtx};
+
+    pub struct Counter<'a> {
+        count: Item<'a, u32>,
+        max: Item<'a, u32>,
+    }
+
+    #[contract]
+    impl Counter<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                count: Item::new("count"),
+                max: Item::new("max"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx, count: u32, max: u32) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &count)?;
+            self.max.save(ctx.deps.storage, &max)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn set(&self, ctx: ExecCtx, count: u32) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &count)?;
+            Ok(Response::new())
+        }
+
+        #[msg(migrate)]
+        fn migrate(&self, ctx: MigrateCtx, max: u32) -> StdResult<Response> {
+            self.max.save(ctx.deps.storage, &max)?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.count.load(ctx.deps.storage)
+        }
+
+        #[msg(query)]
+        fn max(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.max.load(ctx.deps.storage)
+        }
+    }
+}
+
+fn below_max(contract: &CounterProxy<cw_multi_test::App>) -> anyhow::Result<()> {
+    let count = contract.count()?;
+    let max = contract.max()?;
+    ensure!(count <= max, "count {count} exceeds max {max}");
+    Ok(())
+}
+
+fn panic_message(f: impl FnOnce()) -> String {
+    let payload = catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[test]
+fn invariants_hold() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+
+    let contract = code_id
+        .instantiate(1, 5)
+        .with_admin("owner")
+        .call("owner")
+        .unwrap();
+    contract.invariant("below max", below_max);
+
+    contract.set(5).call("owner").unwrap();
+    contract
+        .migrate(7)
+        .call("owner", code_id.code_id())
+        .unwrap();
+    contract.set(7).call("owner").unwrap();
+    app.check_invariants().unwrap();
+}
+
+#[test]
+fn broken_after_exec() {
+    let app = App::default();
+    let contract = CodeId::store_code(&app)
+        .instantiate(1, 5)
+        .call("owner")
+        .unwrap();
+    contract.invariant("below max", below_max);
+
+    let err = contract.set(6).call("owner").unwrap_err();
+    assert!(matches!(err, CallError::Invariant(_)));
+    let msg = err.to_string();
+    assert!(msg.starts_with("Invariant `below max` broken: count 6 exceeds max 5"));
+    assert!(msg.contains(&format!(
+        "After execution on {} by owner with funds []",
+        contract.contract_addr
+    )));
+    assert!(msg.contains(r#"Message: {"set":{"count":6}}"#));
+
+    // Changes of the call breaking the invariant are kept
+    assert_eq!(contract.count().unwrap(), 6);
+    let msg = panic_message(|| app.assert_invariants(|| "manual check".to_owned()));
+    assert!(msg.starts_with("Invariant `below max` broken: count 6 exceeds max 5"));
+    assert!(msg.ends_with("After manual check"));
+}
+
+#[test]
+fn broken_after_migrate() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+    let contract = code_id
+        .instantiate(4, 5)
+        .with_admin("owner")
+        .call("owner")
+        .unwrap();
+    contract.invariant("below max", below_max);
+
+    let err = contract
+        .migrate(3)
+        .call("owner", code_id.code_id())
+        .unwrap_err();
+    assert!(matches!(err, CallError::Invariant(_)));
+    let msg = err.to_string();
+    assert!(msg.starts_with("Invariant `below max` broken: count 4 exceeds max 3"));
+    assert!(msg.contains(&format!(
+        "After migration of {} to code {} by owner",
+        contract.contract_addr,
+        code_id.code_id()
+    )));
+    assert!(msg.contains(r#"Message: {"max":3}"#));
+}
+
+#[test]
+fn broken_after_instantiate() {
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+    app.add_invariant("total below max", |app| {
+        let total: u32 = ["contract0", "contract1"]
+            .into_iter()
+            .map(|addr| {
+                CounterProxy::new(Addr::unchecked(addr), app)
+                    .count()
+                    .unwrap_or_default()
+            })
+            .sum();
+        ensure!(total <= 5, "total count {total} exceeds 5");
+        Ok(())
+    });
+
+    code_id.instantiate(3, 5).call("owner").unwrap();
+    let err = code_id.instantiate(3, 5).call("owner").unwrap_err();
+    assert!(matches!(err, CallError::Invariant(_)));
+    let msg = err.to_string();
+    assert!(msg.starts_with("Invariant `total below max` broken: total count 6 exceeds 5"));
+    assert!(msg.contains(&format!(
+        "After instantiation of contract1 from code {} by owner with funds []",
+        code_id.code_id()
+    )));
+    assert!(msg.contains(r#"Message: {"count":3,"max":5}"#));
+}
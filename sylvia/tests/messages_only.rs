@@ -0,0 +1,185 @@
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct CountResponse {
+    pub count: u64,
+}
+
+pub mod counter {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use sylvia::interface;
+    use sylvia::types::{ExecCtx, QueryCtx};
+
+    use crate::CountResponse;
+
+    #[interface]
+    pub trait Counter {
+        type Error: From<StdError>;
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx) -> StdResult<Response>;
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<CountResponse>;
+    }
+}
+
+/// Full contract, as built with default features
+pub mod contract {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    use crate::counter::Counter;
+    use crate::CountResponse;
+
+    pub struct CounterContract {
+        pub(crate) count: Item<'static, u64>,
+    }
+
+    #[contract]
+    #[messages(crate::counter as Counter)]
+    impl CounterContract {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                count: Item::new("count"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx, count: u64) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &count)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn add(&self, ctx: ExecCtx, value: u64) -> StdResult<Response> {
+            self.count
+                .update(ctx.deps.storage, |count| Ok::<_, StdError>(count + value))?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn doubled(&self, ctx: QueryCtx) -> StdResult<CountResponse> {
+            let count = self.count.load(ctx.deps.storage)?;
+            Ok(CountResponse { count: count * 2 })
+        }
+    }
+
+    #[contract(module=self)]
+    #[messages(crate::counter as Counter)]
+    impl Counter for CounterContract {
+        type Error = StdError;
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.count
+                .update(ctx.deps.storage, |count| Ok::<_, StdError>(count + 1))?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<CountResponse> {
+            let count = self.count.load(ctx.deps.storage)?;
+            Ok(CountResponse { count })
+        }
+    }
+}
+
+/// The same contract as built with the `types` feature - neither the contract type nor its
+/// storage exist here, only the messages and queriers are generated.
+pub mod types {
+    // The impl block is dropped, so only the messages fields and query responses types are
+    // imported - `Response`, `StdResult` and the contexts are not resolved at all
+    use sylvia::contract;
+
+    use crate::CountResponse;
+
+    #[contract(messages_only)]
+    #[messages(crate::counter as Counter)]
+    impl CounterContract {
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx, count: u64) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &count)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn add(&self, ctx: ExecCtx, value: u64) -> StdResult<Response> {
+            self.count.update(ctx.deps.storage, |count| count + value)?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn doubled(&self, ctx: QueryCtx) -> StdResult<CountResponse> {
+            let count = self.count.load(ctx.deps.storage)?;
+            Ok(CountResponse { count: count * 2 })
+        }
+    }
+}
+
+#[test]
+fn messages_match_contract() {
+    use cosmwasm_schema::schema_for;
+    use cosmwasm_std::{from_slice, to_vec};
+
+    assert_eq!(
+        to_vec(&types::InstantiateMsg::new(5)).unwrap(),
+        to_vec(&contract::InstantiateMsg::new(5)).unwrap()
+    );
+    assert_eq!(
+        to_vec(&types::ExecMsg::add(3)).unwrap(),
+        to_vec(&contract::ExecMsg::add(3)).unwrap()
+    );
+
+    let msg: types::ContractExecMsg = from_slice(br#"{ "increment": {} }"#).unwrap();
+    assert_eq!(
+        msg,
+        types::ContractExecMsg::Counter(counter::ExecMsg::Increment {})
+    );
+    let msg: types::ContractQueryMsg = from_slice(br#"{ "sylvia_info": {} }"#).unwrap();
+    assert_eq!(
+        msg,
+        types::ContractQueryMsg::Sylvia(sylvia::types::SylviaQueryMsg::SylviaInfo {})
+    );
+
+    assert_eq!(
+        schema_for!(types::ContractExecMsg),
+        schema_for!(contract::ContractExecMsg)
+    );
+    assert_eq!(
+        schema_for!(types::ContractQueryMsg),
+        schema_for!(contract::ContractQueryMsg)
+    );
+}
+
+#[cfg(feature = "mt")]
+#[test]
+fn query_full_contract() {
+    use cosmwasm_std::Addr;
+    use sylvia::multitest::App;
+
+    use crate::contract::multitest_utils::CodeId;
+    use crate::counter::Querier as _;
+    use crate::types::Querier as _;
+
+    let app = App::default();
+    let code_id = CodeId::store_code(&app);
+    let contract = code_id.instantiate(2).call("owner").unwrap();
+
+    let mt_app = app.app();
+    let querier_wrapper = mt_app.wrap();
+    let remote = types::Remote::new(Addr::unchecked(contract.contract_addr.as_str()));
+    let querier = remote.querier(&querier_wrapper);
+
+    assert_eq!(querier.doubled().unwrap().count, 4);
+    assert_eq!(
+        crate::counter::BoundQuerier::from(&querier)
+            .count()
+            .unwrap()
+            .count,
+        2
+    );
+}
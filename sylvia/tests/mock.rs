@@ -0,0 +1,193 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{coins, Addr, Attribute, Event, Response, StdError};
+use cw_multi_test::Executor;
+use sylvia::multitest::{App, CallError, MockCall};
+
+use crate::market::multitest_utils::CodeId;
+use crate::oracle::trait_utils::OracleMock;
+use crate::oracle::{ExecMsg, PriceResponse};
+
+pub mod oracle {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use sylvia::interface;
+    use sylvia::types::{ExecCtx, QueryCtx};
+
+    #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
+    pub struct PriceResponse {
+        pub price: u128,
+    }
+
+    #[interface]
+    pub trait Oracle {
+        type Error: From<StdError>;
+
+        #[msg(exec)]
+        fn report(&self, ctx: ExecCtx, denom: String, price: u128) -> StdResult<Response>;
+
+        #[msg(query)]
+        fn price(&self, ctx: QueryCtx, denom: String) -> StdResult<PriceResponse>;
+    }
+}
+
+pub mod market {
+    use cosmwasm_std::{to_binary, Addr, Response, StdResult, WasmMsg};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx};
+
+    use crate::oracle::{self, Querier as _};
+
+    pub struct Market<'a> {
+        oracle: Item<'a, Addr>,
+    }
+
+    #[contract]
+    impl Market<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                oracle: Item::new("oracle"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx, oracle: String) -> StdResult<Response> {
+            let oracle = ctx.deps.api.addr_validate(&oracle)?;
+            self.oracle.save(ctx.deps.storage, &oracle)?;
+            Ok(Response::new())
+        }
+
+        /// Pays the oracle price and reports the paid price back
+        #[msg(exec)]
+        fn buy(&self, ctx: ExecCtx, denom: String) -> StdResult<Response> {
+            let oracle = self.oracle.load(ctx.deps.storage)?;
+            let price = oracle::BoundQuerier::borrowed(&oracle, &ctx.deps.querier)
+                .price(denom.clone())?
+                .price;
+
+            let msg = WasmMsg::Execute {
+                contract_addr: oracle.into_string(),
+                msg: to_binary(&oracle::ExecMsg::report(denom, price))?,
+                funds: vec![],
+            };
+            Ok(Response::new()
+                .add_message(msg)
+                .add_attribute("price", price.to_string()))
+        }
+    }
+}
+
+#[test]
+fn canned_responses() {
+    let app = App::<cw_multi_test::App>::default();
+    let oracle = OracleMock::new()
+        .price_returns(PriceResponse { price: 42 })
+        .store_code(&app)
+        .instantiate("owner")
+        .unwrap();
+
+    let market = CodeId::store_code(&app)
+        .instantiate(oracle.contract_addr.to_string())
+        .call("owner")
+        .unwrap();
+
+    let response = market.buy("uatom".to_owned()).call("buyer").unwrap();
+    assert_eq!(response.custom_attrs(1), [Attribute::new("price", "42")]);
+    assert_eq!(
+        oracle.calls(),
+        [MockCall {
+            contract: oracle.contract_addr.clone(),
+            sender: market.contract_addr.clone(),
+            funds: vec![],
+            msg: ExecMsg::report("uatom".to_owned(), 42),
+        }]
+    );
+}
+
+#[test]
+fn handlers() {
+    let app = App::<cw_multi_test::App>::default();
+    let oracle_code = OracleMock::new()
+        .on_price(|_, denom| {
+            let price = if denom == "uatom" { 10 } else { 20 };
+            Ok(PriceResponse { price })
+        })
+        .on_report(|ctx, _, price| {
+            if price > 15 {
+                return Err(StdError::generic_err("Price too high"));
+            }
+            Ok(Response::new().add_attribute("reporter", ctx.info.sender))
+        })
+        .store_code(&app);
+    let oracle = oracle_code.instantiate("owner").unwrap();
+    let other = oracle_code.instantiate("owner").unwrap();
+
+    let code_id = CodeId::store_code(&app);
+    let market = code_id
+        .instantiate(oracle.contract_addr.to_string())
+        .call("owner")
+        .unwrap();
+
+    let response = market.buy("uatom".to_owned()).call("buyer").unwrap();
+    assert!(response.has_event(
+        &Event::new("wasm")
+            .add_attribute("_contract_addr", oracle.contract_addr.as_str())
+            .add_attribute("reporter", market.contract_addr.as_str())
+    ));
+
+    let err = market.buy("uosmo".to_owned()).call("buyer").unwrap_err();
+    assert!(matches!(err, CallError::Nested(_)));
+    assert_eq!(
+        err.downcast_ref::<StdError>(),
+        Some(&StdError::generic_err("Price too high"))
+    );
+
+    // Failed calls are recorded too, each contract recording its own calls
+    let prices: Vec<_> = oracle.calls().into_iter().map(|call| call.msg).collect();
+    assert_eq!(
+        prices,
+        [
+            ExecMsg::report("uatom".to_owned(), 10),
+            ExecMsg::report("uosmo".to_owned(), 20)
+        ]
+    );
+    assert!(other.calls().is_empty());
+}
+
+#[test]
+fn not_mocked() {
+    let owner = Addr::unchecked("owner");
+    let app = App::new(cw_multi_test::App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(10, "uatom"))
+            .unwrap();
+    }));
+    let oracle = OracleMock::new()
+        .store_code(&app)
+        .instantiate("owner")
+        .unwrap();
+
+    // Executions succeed by default
+    app.app_mut()
+        .execute_contract(
+            owner,
+            oracle.contract_addr.clone(),
+            &ExecMsg::report("uatom".to_owned(), 1),
+            &coins(10, "uatom"),
+        )
+        .unwrap();
+    assert_eq!(oracle.calls()[0].funds, coins(10, "uatom"));
+
+    let market = CodeId::store_code(&app)
+        .instantiate(oracle.contract_addr.to_string())
+        .call("owner")
+        .unwrap();
+    let err = market.buy("uatom".to_owned()).call("buyer").unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("No response mocked for `price` query"));
+}
@@ -182,4 +182,32 @@ mod tests {
         let resp = second_contract.counter_proxy().count().unwrap();
         assert_eq!(resp.count, 0);
     }
+
+    #[test]
+    fn supported_interfaces() {
+        let app = App::default();
+        let code_id = CodeId::store_code(&app);
+
+        let contract = code_id
+            .instantiate(Addr::unchecked("remote"))
+            .call("owner")
+            .unwrap();
+
+        let mt_app = app.app();
+        let querier_wrapper = mt_app.wrap();
+        let querier = super::BoundQuerier::borrowed(&contract.contract_addr, &querier_wrapper);
+
+        let info = querier.sylvia_info().unwrap();
+        assert_eq!(info.contract, "sylvia");
+        assert_eq!(info.interfaces.len(), 1);
+        assert_eq!(info.interfaces[0].name, "Counter");
+        assert_eq!(info.interfaces[0].query, vec!["count"]);
+        assert!(querier.supports::<crate::counter::InterfaceApi>().unwrap());
+
+        // Interface querier can check support as well
+        let counter_querier = crate::counter::BoundQuerier::from(&querier);
+        assert!(counter_querier
+            .supports::<crate::counter::InterfaceApi>()
+            .unwrap());
+    }
 }
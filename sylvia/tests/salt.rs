@@ -0,0 +1,187 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{Addr, Binary};
+use sylvia::multitest::{code_checksum, App, Bech32App, CallError};
+
+use crate::factory::multitest_utils::CodeId as FactoryCodeId;
+use crate::member::multitest_utils::CodeId as MemberCodeId;
+
+pub mod member {
+    use cosmwasm_std::{Response, StdResult};
+    use sylvia::contract;
+    use sylvia::types::InstantiateCtx;
+
+    pub struct Member;
+
+    #[contract]
+    impl Member {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+    }
+}
+
+pub mod factory {
+    use cosmwasm_std::{
+        instantiate2_address, to_binary, Addr, Binary, Response, StdError, StdResult, WasmMsg,
+    };
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    pub struct Factory;
+
+    #[contract]
+    impl Factory {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, _ctx: InstantiateCtx) -> StdResult<Response> {
+            Ok(Response::new())
+        }
+
+        /// Creates the member with the instantiate2 address
+        #[msg(exec)]
+        fn create_member(&self, _ctx: ExecCtx, code_id: u64, salt: Binary) -> StdResult<Response> {
+            let msg = WasmMsg::Instantiate2 {
+                admin: None,
+                code_id,
+                label: "member".to_owned(),
+                msg: to_binary(&crate::member::InstantiateMsg::new())?,
+                funds: vec![],
+                salt,
+            };
+            Ok(Response::new().add_message(msg))
+        }
+
+        /// Address of the member created by the factory with the `salt`
+        #[msg(query)]
+        fn member_address(&self, ctx: QueryCtx, checksum: Binary, salt: Binary) -> StdResult<Addr> {
+            let creator = ctx
+                .deps
+                .api
+                .addr_canonicalize(ctx.env.contract.address.as_str())?;
+            let addr = instantiate2_address(&checksum, &creator, &salt)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            ctx.deps.api.addr_humanize(&addr)
+        }
+    }
+}
+
+#[test]
+fn salted_addresses() {
+    let app = App::<Bech32App>::bech32("juno");
+    let owner = app.account("owner");
+    let code_id = MemberCodeId::store_code(&app);
+
+    let predicted = code_id.predict_address(owner.as_str(), b"first").unwrap();
+    assert!(predicted.as_str().starts_with("juno1"));
+
+    let first = code_id
+        .instantiate()
+        .with_salt(b"first")
+        .call(owner.as_str())
+        .unwrap();
+    assert_eq!(first.contract_addr, predicted);
+
+    // Address depends on the creator and the salt only
+    let other = code_id.instantiate().call(owner.as_str()).unwrap();
+    assert_eq!(other.contract_addr, Addr::unchecked("contract1"));
+    assert_ne!(
+        code_id.predict_address(owner.as_str(), b"second").unwrap(),
+        predicted
+    );
+    assert_ne!(
+        code_id
+            .predict_address(app.account("alice").as_str(), b"first")
+            .unwrap(),
+        predicted
+    );
+
+    let err = code_id
+        .instantiate()
+        .with_salt(b"first")
+        .call(owner.as_str())
+        .unwrap_err();
+    assert!(matches!(err, CallError::Framework(_)));
+    assert!(err.to_string().contains("already exists"));
+}
+
+#[test]
+fn predicted_by_contract() {
+    let app = App::<Bech32App>::bech32("juno");
+    let owner = app.account("owner");
+    let member_code_id = MemberCodeId::store_code(&app);
+    let factory = FactoryCodeId::store_code(&app)
+        .instantiate()
+        .call(owner.as_str())
+        .unwrap();
+
+    let checksum = Binary(code_checksum(member_code_id.code_id()));
+    let salt = Binary::from(b"member");
+    let predicted = factory.member_address(checksum, salt.clone()).unwrap();
+    assert_eq!(
+        predicted,
+        member_code_id
+            .predict_address(factory.contract_addr.as_str(), salt.clone())
+            .unwrap()
+    );
+
+    // The `WasmKeeper` rejects `WasmMsg::Instantiate2`, so the factory cannot create the member
+    // itself...
+    let err = factory
+        .create_member(member_code_id.code_id(), salt.clone())
+        .call(owner.as_str())
+        .unwrap_err();
+    assert!(
+        format!("{:?}", err).contains("Unsupported wasm message: Instantiate2"),
+        "{:?}",
+        err
+    );
+
+    // ...but the member instantiated by the proxy on its behalf gets the predicted address
+    let member = member_code_id
+        .instantiate()
+        .with_salt(salt)
+        .call(factory.contract_addr.as_str())
+        .unwrap();
+    assert_eq!(member.contract_addr, predicted);
+}
+
+#[test]
+fn mock_api() {
+    // `MockApi` cannot humanize instantiate2 addresses
+    let app = App::<cw_multi_test::App>::default();
+    let code_id = MemberCodeId::store_code(&app);
+    code_id.predict_address("owner", b"salt").unwrap_err();
+
+    let err = code_id
+        .instantiate()
+        .with_salt(b"salt")
+        .call("owner")
+        .unwrap_err();
+    assert!(matches!(err, CallError::Framework(_)));
+}
+
+#[test]
+#[should_panic(expected = "Salted addresses require the `SaltedAddressGenerator`")]
+fn generator_missing() {
+    let app = App::new(
+        cw_multi_test::AppBuilder::new()
+            .with_api(sylvia::multitest::Bech32Api::new("juno"))
+            .build(|_, _, _| {}),
+    );
+    let owner = app.account("owner");
+    let _ = MemberCodeId::store_code(&app)
+        .instantiate()
+        .with_salt(b"salt")
+        .call(owner.as_str());
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{coins, Addr, Binary, Event, StdError};
+use cw_multi_test::App as MtApp;
+use sylvia::multitest::App;
+
+use crate::vault::multitest_utils::CodeId;
+
+pub mod vault {
+    use cosmwasm_std::{coins, to_binary, BankMsg, Response, StdError, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    pub struct Vault<'a> {
+        withdrawals: Item<'a, u32>,
+    }
+
+    #[contract]
+    impl Vault<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                withdrawals: Item::new("withdrawals"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.withdrawals.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn withdraw(&self, ctx: ExecCtx, amount: u128) -> StdResult<Response> {
+            let withdrawals = self
+                .withdrawals
+                .update(ctx.deps.storage, |withdrawals| -> StdResult<_> {
+                    Ok(withdrawals + 1)
+                })?;
+
+            Ok(Response::new()
+                .add_message(BankMsg::Send {
+                    to_address: ctx.info.sender.into_string(),
+                    amount: coins(amount, "uatom"),
+                })
+                .add_attribute("action", "withdraw")
+                .set_data(to_binary(&withdrawals)?))
+        }
+
+        #[msg(exec)]
+        fn fail(&self, _ctx: ExecCtx) -> StdResult<Response> {
+            Err(StdError::generic_err("Failed"))
+        }
+
+        #[msg(query)]
+        fn withdrawals(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.withdrawals.load(ctx.deps.storage)
+        }
+    }
+}
+
+#[test]
+fn simulate_discards_state() {
+    let owner = Addr::unchecked("owner");
+    let mt_app = cw_multi_test::App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(100, "uatom"))
+            .unwrap();
+    });
+    let app = App::new(mt_app);
+
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .with_funds(&coins(100, "uatom"))
+        .call("owner")
+        .unwrap();
+
+    let withdraw = contract.withdraw(40);
+    let response = withdraw.simulate("owner").unwrap();
+
+    assert!(response.has_event(&Event::new("wasm").add_attribute("action", "withdraw")));
+    assert!(response.has_event(
+        &Event::new("transfer")
+            .add_attribute("recipient", "owner")
+            .add_attribute("amount", "40uatom")
+    ));
+    assert_eq!(response.data, Some(Binary::from(b"1")));
+
+    let balance = |addr: &Addr| {
+        app.app()
+            .wrap()
+            .query_balance(addr, "uatom")
+            .unwrap()
+            .amount
+            .u128()
+    };
+    assert_eq!(contract.withdrawals().unwrap(), 0);
+    assert_eq!(balance(&owner), 0);
+    assert_eq!(balance(&contract.contract_addr), 100);
+
+    // Simulated message can still be executed
+    withdraw.call("owner").unwrap();
+    assert_eq!(contract.withdrawals().unwrap(), 1);
+    assert_eq!(balance(&owner), 40);
+
+    app.record_storage_diffs(true);
+    let response = contract.withdraw(60).simulate("owner").unwrap();
+    assert_eq!(response.data, Some(Binary::from(b"2")));
+    assert!(response.storage_diff.is_some());
+    assert_eq!(balance(&contract.contract_addr), 60);
+}
+
+#[test]
+fn simulate_errors() {
+    let app = App::<MtApp>::default();
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    let err = contract.fail().simulate("owner").unwrap_err();
+    assert_eq!(err, StdError::generic_err("Failed"));
+
+    // Contract has no funds to send
+    contract.withdraw(10).simulate("owner").unwrap_err();
+    assert_eq!(contract.withdrawals().unwrap(), 0);
+}
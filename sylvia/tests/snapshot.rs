@@ -0,0 +1,97 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{coins, Addr};
+use cw_multi_test::next_block;
+use sylvia::multitest::App;
+
+use crate::counter::multitest_utils::CodeId;
+
+pub mod counter {
+    use cosmwasm_std::{Response, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    pub struct Counter<'a> {
+        count: Item<'a, u32>,
+    }
+
+    #[contract]
+    impl Counter<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                count: Item::new("count"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.count
+                .update(ctx.deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.count.load(ctx.deps.storage)
+        }
+    }
+}
+
+#[test]
+fn restore_snapshot() {
+    let owner = Addr::unchecked("owner");
+    let app = App::new(cw_multi_test::App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(100, "uatom"))
+            .unwrap();
+    }));
+    let balance = |addr: &str| {
+        app.app()
+            .wrap()
+            .query_balance(addr, "uatom")
+            .unwrap()
+            .amount
+            .u128()
+    };
+
+    let code_id = CodeId::store_code(&app);
+    let contract = code_id.instantiate().call("owner").unwrap();
+    contract.increment().call("owner").unwrap();
+
+    let snapshot = app.snapshot();
+    let block = app.block_info();
+
+    for _ in 0..2 {
+        contract
+            .increment()
+            .with_funds(&coins(30, "uatom"))
+            .call("owner")
+            .unwrap();
+        let other = code_id.instantiate().call("owner").unwrap();
+        app.update_block(next_block);
+
+        assert_eq!(contract.count().unwrap(), 2);
+        assert_eq!(balance("owner"), 70);
+        assert_eq!(other.contract_addr, "contract1");
+
+        let coverage = app.coverage();
+        app.restore(&snapshot);
+
+        // Coverage records the calls done by the test, so it is not rolled back
+        assert_eq!(app.coverage(), coverage);
+        assert_eq!(contract.count().unwrap(), 1);
+        assert_eq!(balance("owner"), 100);
+        assert_eq!(balance(contract.contract_addr.as_str()), 0);
+        assert_eq!(app.block_info(), block);
+        assert!(other.count().is_err());
+    }
+}
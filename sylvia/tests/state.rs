@@ -0,0 +1,144 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::Addr;
+use sylvia::multitest::{App, ContractState, StateModel};
+
+use crate::counters::multitest_utils::CodeId;
+
+pub mod counters {
+    use cosmwasm_std::{Order, Response, StdResult};
+    use cw_storage_plus::{Item, Map};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    pub struct Counters<'a> {
+        total: Item<'a, u32>,
+        counters: Map<'a, &'a str, u32>,
+    }
+
+    #[contract]
+    impl Counters<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                total: Item::new("total"),
+                counters: Map::new("counters"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.total.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx, counter: String) -> StdResult<Response> {
+            self.counters
+                .update(ctx.deps.storage, &counter, |count| -> StdResult<_> {
+                    Ok(count.unwrap_or_default() + 1)
+                })?;
+            self.total
+                .update(ctx.deps.storage, |total| -> StdResult<_> { Ok(total + 1) })?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn total(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.total.load(ctx.deps.storage)
+        }
+
+        #[msg(query)]
+        fn counters(&self, ctx: QueryCtx) -> StdResult<Vec<(String, u32)>> {
+            self.counters
+                .range(ctx.deps.storage, None, None, Order::Ascending)
+                .collect()
+        }
+    }
+}
+
+#[test]
+fn export_and_import() {
+    let app = App::<cw_multi_test::App>::default();
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    contract.increment("a".to_owned()).call("owner").unwrap();
+    contract.increment("b".to_owned()).call("owner").unwrap();
+    contract.increment("a".to_owned()).call("owner").unwrap();
+
+    let json = app.export_state(&contract.contract_addr).to_json();
+    let state = ContractState::from_json(&json).unwrap();
+    assert_eq!(state.models.len(), 3);
+
+    // Replayed in a new app, next to the contracts instantiated before
+    let new_app = App::<cw_multi_test::App>::default();
+    let code_id = CodeId::store_code(&new_app);
+    let other = code_id.instantiate().call("owner").unwrap();
+    let imported = code_id.instantiate().call("owner").unwrap();
+    imported.increment("c".to_owned()).call("owner").unwrap();
+    new_app.import_state(&imported.contract_addr, &state);
+
+    assert_eq!(imported.total().unwrap(), 3);
+    assert_eq!(
+        imported.counters().unwrap(),
+        [("a".to_owned(), 2), ("b".to_owned(), 1)]
+    );
+    assert_eq!(new_app.export_state(&imported.contract_addr), state);
+    assert_eq!(other.total().unwrap(), 0);
+    assert!(other.counters().unwrap().is_empty());
+
+    imported.increment("b".to_owned()).call("owner").unwrap();
+    assert_eq!(imported.total().unwrap(), 4);
+}
+
+#[test]
+fn wasm_storage_layout() {
+    let app = App::<cw_multi_test::App>::default();
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    // Only the `total` item is written on instantiation
+    let state = app.export_state(&contract.contract_addr);
+    assert_eq!(
+        state.models,
+        [StateModel {
+            key: b"total".to_vec().into(),
+            value: b"0".to_vec().into(),
+        }],
+        "Raw storage layout of the `WasmKeeper` changed, update `sylvia::multitest::wasm_storage`"
+    );
+}
+
+#[test]
+fn chain_export() {
+    // Response of the `/cosmwasm/wasm/v1/contract/{address}/state` query
+    let json = r#"{
+        "models": [
+            { "key": "0008636f756e746572736d61696e", "value": "Nw==" },
+            { "key": "746f74616c", "value": "Nw==" }
+        ],
+        "pagination": { "next_key": null, "total": "2" }
+    }"#;
+    let state = ContractState::from_json(json).unwrap();
+
+    let app = App::<cw_multi_test::App>::default();
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    app.import_state(&contract.contract_addr, &state);
+
+    assert_eq!(contract.total().unwrap(), 7);
+    assert_eq!(contract.counters().unwrap(), [("main".to_owned(), 7)]);
+}
+
+#[test]
+#[should_panic(expected = "Contract contract0 does not exist")]
+fn missing_contract() {
+    let app = App::<cw_multi_test::App>::default();
+    app.import_state(&Addr::unchecked("contract0"), &ContractState::default());
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "mt")]
+
+use sylvia::multitest::{App, KeyChange};
+
+use crate::registry::multitest_utils::CodeId;
+
+pub mod registry {
+    use cosmwasm_std::{Response, StdResult};
+    use cw_storage_plus::{Item, Map};
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx};
+
+    pub struct Registry<'a> {
+        admin: Item<'a, String>,
+        names: Map<'a, &'a str, String>,
+        scores: Map<'a, (&'a str, &'a str), u32>,
+    }
+
+    #[contract]
+    impl Registry<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                admin: Item::new("admin"),
+                names: Map::new("names"),
+                scores: Map::new("scores"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.admin
+                .save(ctx.deps.storage, &ctx.info.sender.into_string())?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn register(&self, ctx: ExecCtx, name: String) -> StdResult<Response> {
+            self.names
+                .save(ctx.deps.storage, ctx.info.sender.as_str(), &name)?;
+            self.scores
+                .save(ctx.deps.storage, (ctx.info.sender.as_str(), "game"), &1)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn unregister(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.names
+                .remove(ctx.deps.storage, ctx.info.sender.as_str());
+            Ok(Response::new())
+        }
+    }
+}
+
+#[test]
+fn record_diffs() {
+    let app = App::<cw_multi_test::App>::default();
+    let code_id = CodeId::store_code(&app);
+
+    // Recording is disabled by default
+    let response = code_id.instantiate().call_with_response("owner").unwrap();
+    assert_eq!(response.storage_diff, None);
+
+    app.record_storage_diffs(true);
+
+    let response = code_id.instantiate().call_with_response("owner").unwrap();
+    let contract = response.response;
+    let diff = response.storage_diff.unwrap();
+    assert_eq!(
+        diff.contract(&contract.contract_addr),
+        [KeyChange {
+            key: b"admin".to_vec(),
+            old: None,
+            new: Some(br#""owner""#.to_vec()),
+        }]
+    );
+    // Contract metadata is stored by the wasm module
+    assert_eq!(diff.modules["wasm"].len(), 1);
+
+    let response = contract
+        .register("Alice".to_owned())
+        .call_with_response("alice")
+        .unwrap();
+    let diff = response.storage_diff.unwrap();
+    let changes = diff.contract(&contract.contract_addr);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].new, Some(br#""Alice""#.to_vec()));
+    // Length prefixed `names` namespace followed by the key
+    assert_eq!(changes[0].growth(), 12 + 7);
+    assert!(diff.modules.is_empty());
+    assert_eq!(diff.written_bytes(), 19 + 20);
+    assert_eq!(diff.growth(), 19 + 20);
+
+    let response = contract
+        .register("Bob".to_owned())
+        .call_with_response("alice")
+        .unwrap();
+    let diff = response.storage_diff.unwrap();
+    assert_eq!(diff.contract(&contract.contract_addr).len(), 1);
+    assert_eq!(diff.growth(), -2);
+
+    let response = contract.unregister().call_with_response("alice").unwrap();
+    assert_eq!(
+        response.storage_diff.unwrap().to_string(),
+        format!(
+            "contract {}:\n  - names[\"alice\"] (5 bytes)\n0 bytes written, storage grown by -17 bytes",
+            contract.contract_addr
+        )
+    );
+
+    // Removing missing key changes nothing
+    let response = contract.unregister().call_with_response("alice").unwrap();
+    let diff = response.storage_diff.unwrap();
+    assert!(diff.is_empty());
+    assert!(diff.contract(&contract.contract_addr).is_empty());
+
+    app.record_storage_diffs(false);
+    let response = contract.unregister().call_with_response("alice").unwrap();
+    assert_eq!(response.storage_diff, None);
+}
+
+#[test]
+fn display_diff() {
+    let app = App::<cw_multi_test::App>::default();
+    app.record_storage_diffs(true);
+
+    let contract = CodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    contract.register("Alice".to_owned()).call("alice").unwrap();
+    let response = contract
+        .register("Al".to_owned())
+        .call_with_response("alice")
+        .unwrap();
+
+    assert_eq!(
+        response.storage_diff.unwrap().to_string(),
+        format!(
+            "contract {}:\n  ~ names[\"alice\"] (7 -> 4 bytes)\n16 bytes written, storage grown by -3 bytes",
+            contract.contract_addr
+        )
+    );
+
+    let response = contract
+        .register("Alice".to_owned())
+        .call_with_response("bob")
+        .unwrap();
+    assert_eq!(
+        response.storage_diff.unwrap().to_string(),
+        format!(
+            "contract {}:\n  + names[\"bob\"] (7 bytes)\n  + scores[\"bob\", \"game\"] (1 bytes)\n35 bytes written, storage grown by 35 bytes",
+            contract.contract_addr
+        )
+    );
+}
@@ -0,0 +1,251 @@
+#![cfg(feature = "mt")]
+
+use cosmwasm_std::{Binary, ReplyOn, StdError};
+use sylvia::multitest::{reply_err, reply_ok, App, CallError};
+
+use crate::caller::multitest_utils::CodeId as CallerCodeId;
+use crate::counter::multitest_utils::CodeId as CounterCodeId;
+use crate::counter::ExecMsg as CounterExecMsg;
+
+pub mod counter {
+    use cosmwasm_std::{Response, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx};
+
+    pub struct Counter<'a> {
+        count: Item<'a, u32>,
+    }
+
+    #[contract]
+    impl Counter<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                count: Item::new("count"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.count.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn increment(&self, ctx: ExecCtx) -> StdResult<Response> {
+            self.count
+                .update(ctx.deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.count.load(ctx.deps.storage)
+        }
+    }
+}
+
+pub mod caller {
+    use cosmwasm_std::{
+        to_binary, Addr, Binary, Reply, Response, StdError, StdResult, SubMsg, SubMsgResult,
+        WasmMsg,
+    };
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx, ReplyCtx};
+
+    use crate::CounterExecMsg;
+
+    pub const FORWARD_ID: u64 = 1;
+    pub const FAILING_ID: u64 = 2;
+
+    pub struct Caller<'a> {
+        counter: Item<'a, Addr>,
+        last_reply: Item<'a, (u64, Option<Binary>)>,
+    }
+
+    #[contract]
+    impl Caller<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                counter: Item::new("counter"),
+                last_reply: Item::new("last_reply"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx, counter: String) -> StdResult<Response> {
+            let counter = ctx.deps.api.addr_validate(&counter)?;
+            self.counter.save(ctx.deps.storage, &counter)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn poke(&self, ctx: ExecCtx) -> StdResult<Response> {
+            let increment = self.increment(ctx.deps.storage)?;
+            Ok(Response::new()
+                .add_submessage(SubMsg::reply_always(increment.clone(), FORWARD_ID))
+                .add_message(increment))
+        }
+
+        #[msg(reply)]
+        fn reply(&self, ctx: ReplyCtx, reply: Reply) -> StdResult<Response> {
+            let data = match reply.result {
+                SubMsgResult::Err(err) if reply.id == FAILING_ID => {
+                    return Err(StdError::generic_err(format!("Failed: {err}")))
+                }
+                SubMsgResult::Err(_) => None,
+                SubMsgResult::Ok(response) => response.data,
+            };
+            self.last_reply
+                .save(ctx.deps.storage, &(reply.id, data.clone()))?;
+
+            // Forwarded replies increment the counter again
+            let mut response = Response::new();
+            if reply.id == FORWARD_ID && data.is_some() {
+                response = response.add_message(self.increment(ctx.deps.storage)?);
+            }
+            Ok(response)
+        }
+
+        #[msg(query)]
+        fn last_reply(&self, ctx: QueryCtx) -> StdResult<Option<(u64, Option<Binary>)>> {
+            self.last_reply.may_load(ctx.deps.storage)
+        }
+    }
+
+    impl Caller<'_> {
+        fn increment(&self, storage: &dyn cosmwasm_std::Storage) -> StdResult<WasmMsg> {
+            Ok(WasmMsg::Execute {
+                contract_addr: self.counter.load(storage)?.into_string(),
+                msg: to_binary(&CounterExecMsg::Increment {})?,
+                funds: vec![],
+            })
+        }
+    }
+}
+
+#[test]
+fn dispatched_submsgs() {
+    let app = App::<cw_multi_test::App>::default();
+    let counter = CounterCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    let caller = CallerCodeId::store_code(&app)
+        .instantiate(counter.contract_addr.to_string())
+        .call_with_response("owner")
+        .unwrap();
+    assert!(caller.submsgs.is_empty());
+    let caller = caller.response;
+
+    let submsgs = caller.poke().call_with_response("owner").unwrap().submsgs;
+    assert_eq!(counter.count().unwrap(), 2);
+    assert_eq!(
+        caller.last_reply().unwrap(),
+        Some((caller::FORWARD_ID, None))
+    );
+
+    assert_eq!(submsgs.len(), 2);
+    assert_eq!(submsgs[0].sender, caller.contract_addr);
+    assert_eq!(submsgs[0].id, caller::FORWARD_ID);
+    assert_eq!(submsgs[0].reply_on, ReplyOn::Always);
+    assert_eq!(
+        submsgs[0].contract_addr(),
+        Some(counter.contract_addr.as_str())
+    );
+    assert_eq!(
+        submsgs[0].exec_msg::<CounterExecMsg>(),
+        Some(CounterExecMsg::Increment {})
+    );
+    assert_eq!(submsgs[0].custom_msg::<CounterExecMsg>(), None);
+    assert_eq!(submsgs[1].reply_on, ReplyOn::Never);
+
+    let response = counter.increment().call_with_response("owner").unwrap();
+    assert!(response.submsgs.is_empty());
+}
+
+#[test]
+fn injected_reply() {
+    let app = App::<cw_multi_test::App>::default();
+    let counter = CounterCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    let caller = CallerCodeId::store_code(&app)
+        .instantiate(counter.contract_addr.to_string())
+        .call("owner")
+        .unwrap();
+
+    let data = Binary::from(b"data");
+    caller.reply(reply_ok(5, Some(data.clone()))).unwrap();
+    assert_eq!(caller.last_reply().unwrap(), Some((5, Some(data.clone()))));
+
+    caller.reply(reply_err(7, "Out of gas")).unwrap();
+    assert_eq!(caller.last_reply().unwrap(), Some((7, None)));
+
+    // Failed reply reverts its changes
+    let err = caller
+        .reply(reply_err(caller::FAILING_ID, "Out of gas"))
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("Failed: Out of gas"));
+    assert!(matches!(err, CallError::Contract(_)));
+    assert_eq!(caller.last_reply().unwrap(), Some((7, None)));
+
+    // Messages returned by the reply are executed
+    let response = caller
+        .reply_with_response(reply_ok(caller::FORWARD_ID, Some(data)))
+        .unwrap();
+    assert_eq!(counter.count().unwrap(), 1);
+    assert_eq!(
+        response.submsgs[0].exec_msg::<CounterExecMsg>(),
+        Some(CounterExecMsg::Increment {})
+    );
+}
+
+#[test]
+fn reply_to_untracked_contract() {
+    use cosmwasm_std::{Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+    use cw_multi_test::{ContractWrapper, Executor};
+
+    fn sudo(deps: DepsMut, _env: Env, _msg: Empty) -> StdResult<Response> {
+        deps.storage.set(b"sudo", b"called");
+        Ok(Response::new())
+    }
+
+    let app = App::<cw_multi_test::App>::default();
+    let code = ContractWrapper::new(
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_: Deps, _: Env, _: Empty| -> StdResult<Binary> { Ok(Binary::default()) },
+    )
+    .with_sudo(sudo);
+    let code_id = app.app_mut().store_code(Box::new(code));
+    let contract = app
+        .app_mut()
+        .instantiate_contract(
+            code_id,
+            cosmwasm_std::Addr::unchecked("owner"),
+            &Empty {},
+            &[],
+            "untracked",
+            None,
+        )
+        .unwrap();
+
+    let err = app.reply(&contract, reply_ok(1, None)).unwrap_err();
+    assert!(
+        err.to_string().starts_with("Reply cannot be injected"),
+        "{err}"
+    );
+
+    // The contract is not called at all
+    let sudo_called = app.app().wrap().query_wasm_raw(&contract, b"sudo").unwrap();
+    assert_eq!(sudo_called, None);
+}
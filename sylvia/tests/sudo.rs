@@ -0,0 +1,211 @@
+#![cfg(feature = "mt")]
+
+use anyhow::ensure;
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{from_binary, StdError};
+use sylvia::multitest::{App, CallError};
+
+use crate::governed::multitest_utils::CodeId as GovernedCodeId;
+use crate::governed::SudoMsg;
+use crate::overridden::multitest_utils::CodeId as OverriddenCodeId;
+
+pub mod governed {
+    use cosmwasm_std::{Response, StdError, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::types::{ExecCtx, InstantiateCtx, QueryCtx, SudoCtx};
+    use sylvia::{contract, entry_points};
+
+    pub struct Governed<'a> {
+        frozen: Item<'a, bool>,
+        limit: Item<'a, u32>,
+    }
+
+    #[entry_points]
+    #[contract]
+    impl Governed<'_> {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self {
+                frozen: Item::new("frozen"),
+                limit: Item::new("limit"),
+            }
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            self.frozen.save(ctx.deps.storage, &false)?;
+            self.limit.save(ctx.deps.storage, &10)?;
+            Ok(Response::new())
+        }
+
+        #[msg(exec)]
+        fn spend(&self, ctx: ExecCtx, amount: u32) -> StdResult<Response> {
+            if self.frozen.load(ctx.deps.storage)? {
+                return Err(StdError::generic_err("Frozen"));
+            }
+            self.limit
+                .update(ctx.deps.storage, |limit| -> StdResult<_> {
+                    limit
+                        .checked_sub(amount)
+                        .ok_or_else(|| StdError::generic_err("Limit exceeded"))
+                })?;
+            Ok(Response::new())
+        }
+
+        #[msg(sudo)]
+        fn freeze(&self, ctx: SudoCtx, frozen: bool) -> StdResult<Response> {
+            self.frozen.save(ctx.deps.storage, &frozen)?;
+            Ok(Response::new().add_attribute("frozen", frozen.to_string()))
+        }
+
+        #[msg(sudo)]
+        fn set_limit(&self, ctx: SudoCtx, limit: u32) -> StdResult<Response> {
+            if limit == 0 {
+                return Err(StdError::generic_err("Zero limit"));
+            }
+            self.limit.save(ctx.deps.storage, &limit)?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn limit(&self, ctx: QueryCtx) -> StdResult<u32> {
+            self.limit.load(ctx.deps.storage)
+        }
+    }
+}
+
+pub mod overridden {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{DepsMut, Env, Response, StdResult};
+    use cw_storage_plus::Item;
+    use sylvia::contract;
+    use sylvia::types::{InstantiateCtx, QueryCtx};
+
+    const COUNTER: Item<u32> = Item::new("counter");
+
+    #[cw_serde]
+    pub enum CustomSudoMsg {
+        Reset { count: u32 },
+    }
+
+    pub fn sudo(deps: DepsMut, _env: Env, msg: CustomSudoMsg) -> StdResult<Response> {
+        let CustomSudoMsg::Reset { count } = msg;
+        COUNTER.save(deps.storage, &count)?;
+        Ok(Response::new())
+    }
+
+    pub struct Overridden;
+
+    #[contract]
+    #[sv::override_entry_point(sudo=crate::overridden::sudo(crate::overridden::CustomSudoMsg))]
+    impl Overridden {
+        #[allow(clippy::new_without_default)]
+        pub const fn new() -> Self {
+            Self
+        }
+
+        #[msg(instantiate)]
+        fn instantiate(&self, ctx: InstantiateCtx) -> StdResult<Response> {
+            COUNTER.save(ctx.deps.storage, &0)?;
+            Ok(Response::new())
+        }
+
+        #[msg(query)]
+        fn count(&self, ctx: QueryCtx) -> StdResult<u32> {
+            COUNTER.load(ctx.deps.storage)
+        }
+    }
+}
+
+#[test]
+fn declared_sudo() {
+    let app = App::<cw_multi_test::App>::default();
+    let contract = GovernedCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    let response = contract.freeze(true).call().unwrap();
+    assert!(response
+        .events
+        .iter()
+        .any(|event| event.attributes.iter().any(|attr| attr.key == "frozen")));
+    let err = contract.spend(1).call("owner").unwrap_err();
+    assert_eq!(err, StdError::generic_err("Frozen"));
+
+    contract.freeze(false).call().unwrap();
+    contract.set_limit(20).call().unwrap();
+    contract.spend(15).call("owner").unwrap();
+    assert_eq!(contract.limit().unwrap(), 5);
+
+    let err = contract.set_limit(0).call().unwrap_err();
+    assert!(matches!(err, CallError::Contract(_)));
+    assert_eq!(err, StdError::generic_err("Zero limit"));
+    assert_eq!(contract.limit().unwrap(), 5);
+
+    // Generated `SudoMsg` is accepted by the `sudo` entry point
+    (*contract.app)
+        .app_mut()
+        .wasm_sudo(contract.contract_addr.clone(), &SudoMsg::set_limit(7))
+        .unwrap();
+    assert_eq!(contract.limit().unwrap(), 7);
+    assert_eq!(SudoMsg::messages(), ["freeze", "set_limit"]);
+}
+
+#[test]
+fn sudo_entry_point() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    governed::entry_points::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        governed::InstantiateMsg {},
+    )
+    .unwrap();
+
+    governed::entry_points::sudo(deps.as_mut(), env, SudoMsg::set_limit(3)).unwrap();
+    let limit: u32 = from_binary(
+        &governed::entry_points::query(
+            deps.as_ref(),
+            mock_env(),
+            governed::ContractQueryMsg::Governed(governed::QueryMsg::limit()),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(limit, 3);
+}
+
+#[test]
+fn overridden_sudo() {
+    let app = App::<cw_multi_test::App>::default();
+    let contract = OverriddenCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+
+    contract
+        .sudo(overridden::CustomSudoMsg::Reset { count: 4 })
+        .call()
+        .unwrap();
+    assert_eq!(contract.count().unwrap(), 4);
+}
+
+#[test]
+fn broken_after_sudo() {
+    let app = App::<cw_multi_test::App>::default();
+    let contract = GovernedCodeId::store_code(&app)
+        .instantiate()
+        .call("owner")
+        .unwrap();
+    contract.invariant("limit at most 100", |contract| {
+        ensure!(contract.limit()? <= 100, "limit too high");
+        Ok(())
+    });
+
+    contract.set_limit(100).call().unwrap();
+    let err = contract.set_limit(101).call().unwrap_err();
+    assert!(matches!(err, CallError::Invariant(_)));
+    assert!(err.to_string().contains("After sudo on contract0"));
+}